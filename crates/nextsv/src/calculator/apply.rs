@@ -0,0 +1,528 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use git2::{Index, IndexEntry, IndexTime, Oid, Repository};
+use toml_edit::{DocumentMut, Item, Value};
+
+use crate::{Calculator, Error, Workspace};
+
+use super::{conventional::get_subdir_for_package, Bump, NextVersion};
+
+/// Default message for the release commit
+const DEFAULT_RELEASE_MESSAGE: &str = "chore: release {version}";
+
+/// Captures the options for applying the calculated version to the project
+///
+/// The options are set following the builder pattern and are passed to
+/// [`Calculator::apply`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ApplyConfig {
+    /// Template for the release commit message [default: "chore: release {version}"]
+    pub(crate) message: String,
+    /// Report the changes without writing, committing or tagging [default: false]
+    pub(crate) dry_run: bool,
+}
+
+impl Default for ApplyConfig {
+    fn default() -> Self {
+        ApplyConfig {
+            message: DEFAULT_RELEASE_MESSAGE.to_string(),
+            dry_run: false,
+        }
+    }
+}
+
+impl ApplyConfig {
+    /// Initialise a new apply config with the default commit message.
+    pub fn new() -> ApplyConfig {
+        ApplyConfig::default()
+    }
+
+    /// Set the template for the release commit message.
+    ///
+    /// The placeholders `{version}` and `{tag}` are replaced with the next
+    /// version number and the name of the new tag.
+    pub fn set_message(mut self, message: &str) -> Self {
+        self.message = message.to_string();
+        self
+    }
+
+    /// Set the dry run flag.
+    ///
+    /// A dry run calculates the changes to the manifests but does not write
+    /// them, commit them or create the tag.
+    pub fn set_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    fn message(&self, version: &str, tag: &str) -> String {
+        self.message
+            .replace("{version}", version)
+            .replace("{tag}", tag)
+    }
+}
+
+/// The change made to a file when applying the version
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FileChange {
+    /// Path to the file relative to the root of the repository
+    pub path: PathBuf,
+    /// Content of the file before the change
+    pub before: String,
+    /// Content of the file after the change
+    pub after: String,
+}
+
+impl FileChange {
+    /// Show the lines changed in the file in the style of a unified diff.
+    pub fn diff(&self) -> String {
+        let path = self.path.display();
+        let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+        for (i, (before, after)) in self.before.lines().zip(self.after.lines()).enumerate() {
+            if before != after {
+                diff.push_str(&format!("@@ -{0} +{0} @@\n-{before}\n+{after}\n", i + 1));
+            }
+        }
+        diff
+    }
+}
+
+/// Result of applying the calculated version to the project
+///
+/// Created by calling the [`Calculator::apply`] method.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Applied {
+    /// The version written to the manifests
+    pub version: String,
+    /// The name of the tag for the version
+    pub tag: String,
+    /// The message for the release commit
+    pub message: String,
+    /// The changes made to the manifests and lock file
+    pub changes: Vec<FileChange>,
+    /// True if the changes were not written, committed or tagged
+    pub dry_run: bool,
+}
+
+impl fmt::Display for Applied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.dry_run {
+            for change in &self.changes {
+                write!(f, "{}", change.diff())?;
+            }
+            write!(
+                f,
+                "Dry run: would commit `{}` and create tag `{}`",
+                self.message, self.tag
+            )
+        } else {
+            write!(
+                f,
+                "Committed `{}` and created tag `{}`",
+                self.message, self.tag
+            )
+        }
+    }
+}
+
+impl Calculator {
+    /// Apply the next version to the project.
+    ///
+    /// The version is written to `package.version` in the manifest of the
+    /// selected package, or to `workspace.package.version` where the version is
+    /// inherited from the workspace. Matching entries in `Cargo.lock` are
+    /// updated. The changes are then committed and tagged with the next version
+    /// tag so that the tag is found by the next calculation. The tag is annotated
+    /// if only annotated tags are accepted as version tags.
+    ///
+    /// Returns `None` if no release is required.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// # use nextsv::{ApplyConfig, CalculatorConfig};
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .build()?;
+    ///
+    ///     let apply_config = ApplyConfig::new().set_dry_run(true);
+    ///     if let Some(applied) = calculator.apply(&apply_config)? {
+    ///         println!("{applied}");
+    ///     }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply(&self, apply_config: &ApplyConfig) -> Result<Option<Applied>, Error> {
+        let NextVersion::Updated(next_version) = &self.next_version else {
+            return Ok(None);
+        };
+        if self.bump == Bump::None {
+            log::info!("No release required so there is nothing to apply");
+            return Ok(None);
+        }
+
+        let version = next_version.semantic_version.to_string();
+        let tag = next_version.tag_name();
+        let message = apply_config.message(&version, &tag);
+
+        let (repo, workdir) = self.config.open_repository()?;
+        // Fail before anything is written so a clash cannot leave an untagged release commit.
+        if repo.find_reference(&format!("refs/tags/{tag}")).is_ok() {
+            log::error!("The tag `{tag}` for the next version already exists");
+            return Err(Error::TagExists(tag));
+        }
+        let package_dir = get_subdir_for_package(
+            self.config.repository_dir(),
+            &workdir,
+            self.config.package.as_deref(),
+            self.config.subdir.as_deref(),
+        )?;
+        let changes = manifest_changes(&workdir, package_dir.as_deref(), &version)?;
+
+        if !apply_config.dry_run {
+            for change in &changes {
+                log::debug!("Writing version `{version}` to `{}`", change.path.display());
+                fs::write(workdir.join(&change.path), &change.after)?;
+            }
+            commit_and_tag(&repo, &changes, &message, &tag, self.config.annotated_only)?;
+        }
+
+        Ok(Some(Applied {
+            version,
+            tag,
+            message,
+            changes,
+            dry_run: apply_config.dry_run,
+        }))
+    }
+}
+
+/// Calculate the changes to the manifests and lock file to set the version.
+///
+/// The paths of the changes are relative to the working directory `workdir`.
+fn manifest_changes(
+    workdir: &Path,
+    package_dir: Option<&str>,
+    version: &str,
+) -> Result<Vec<FileChange>, Error> {
+    let root_path = PathBuf::from("Cargo.toml");
+    let manifest_path = match package_dir {
+        Some(dir) => {
+            let dir = Path::new(dir);
+            dir.strip_prefix("./").unwrap_or(dir).join("Cargo.toml")
+        }
+        None => root_path.clone(),
+    };
+
+    let mut changes = vec![];
+    let mut packages = vec![];
+
+    let before = fs::read_to_string(workdir.join(&manifest_path))?;
+    let mut manifest: DocumentMut = before.parse()?;
+
+    if let Some(old_version) = package_version(&manifest) {
+        packages.push((package_name(&manifest), old_version));
+        set_version(&mut manifest["package"]["version"], version);
+        let after = manifest.to_string();
+        if after != before {
+            changes.push(FileChange {
+                path: manifest_path,
+                before,
+                after,
+            });
+        }
+    } else if manifest_path == root_path || inherits_version(&manifest) {
+        let (before, mut workspace) = if manifest_path == root_path {
+            (before, manifest)
+        } else {
+            let before = fs::read_to_string(workdir.join(&root_path))?;
+            let workspace = before.parse()?;
+            (before, workspace)
+        };
+
+        let Some(old_version) = workspace
+            .get("workspace")
+            .and_then(|workspace| workspace.get("package"))
+            .and_then(|package| package.get("version"))
+            .and_then(Item::as_str)
+            .map(String::from)
+        else {
+            return Err(Error::NoManifestVersion(root_path.display().to_string()));
+        };
+
+        if inherits_version(&workspace) {
+            packages.push((package_name(&workspace), old_version.clone()));
+        }
+        for member in Workspace::new(&workdir.join(&root_path))?.members()? {
+            let member_path = workdir.join(&member).join("Cargo.toml");
+            if !member_path.is_file() {
+                continue;
+            }
+            let member_manifest: DocumentMut = fs::read_to_string(&member_path)?.parse()?;
+            if inherits_version(&member_manifest) {
+                packages.push((package_name(&member_manifest), old_version.clone()));
+            }
+        }
+
+        set_version(&mut workspace["workspace"]["package"]["version"], version);
+        let after = workspace.to_string();
+        if after != before {
+            changes.push(FileChange {
+                path: root_path,
+                before,
+                after,
+            });
+        }
+    } else {
+        return Err(Error::NoManifestVersion(
+            manifest_path.display().to_string(),
+        ));
+    }
+
+    let lock_path = PathBuf::from("Cargo.lock");
+    if workdir.join(&lock_path).is_file() {
+        let before = fs::read_to_string(workdir.join(&lock_path))?;
+        let after = set_lock_versions(&before, &packages, version)?;
+        if after != before {
+            changes.push(FileChange {
+                path: lock_path,
+                before,
+                after,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+/// The version set in the package table, if not inherited from the workspace.
+fn package_version(manifest: &DocumentMut) -> Option<String> {
+    manifest
+        .get("package")
+        .and_then(|package| package.get("version"))
+        .and_then(Item::as_str)
+        .map(String::from)
+}
+
+/// True if the package inherits the version from the workspace.
+fn inherits_version(manifest: &DocumentMut) -> bool {
+    manifest
+        .get("package")
+        .and_then(|package| package.get("version"))
+        .and_then(|version| version.get("workspace"))
+        .and_then(Item::as_bool)
+        .unwrap_or_default()
+}
+
+fn package_name(manifest: &DocumentMut) -> String {
+    manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Replace the version value keeping the surrounding formatting.
+fn set_version(item: &mut Item, version: &str) {
+    if let Some(value) = item.as_value_mut() {
+        let decor = value.decor().clone();
+        *value = Value::from(version);
+        *value.decor_mut() = decor;
+    }
+}
+
+/// Update the lock file entries for the packages from the old to the new version.
+fn set_lock_versions(
+    content: &str,
+    packages: &[(String, String)],
+    version: &str,
+) -> Result<String, Error> {
+    let mut lock: DocumentMut = content.parse()?;
+
+    if let Some(entries) = lock
+        .get_mut("package")
+        .and_then(Item::as_array_of_tables_mut)
+    {
+        for entry in entries.iter_mut() {
+            let name = entry.get("name").and_then(Item::as_str);
+            let old_version = entry.get("version").and_then(Item::as_str);
+            let matched = packages.iter().any(|(package, package_version)| {
+                Some(package.as_str()) == name && Some(package_version.as_str()) == old_version
+            });
+            if matched {
+                if let Some(item) = entry.get_mut("version") {
+                    set_version(item, version);
+                }
+            }
+        }
+    }
+
+    Ok(lock.to_string())
+}
+
+/// Commit the changed files and tag the commit.
+///
+/// The tag is annotated with the commit message if only annotated tags are
+/// accepted as version tags, else it is a lightweight tag.
+///
+/// The tree of the commit is the tree of `HEAD` with only the changed files
+/// replaced, so that anything else already staged is left out of the release
+/// commit and stays staged.
+fn commit_and_tag(
+    repo: &Repository,
+    changes: &[FileChange],
+    message: &str,
+    tag: &str,
+    annotated: bool,
+) -> Result<(), Error> {
+    let parent = repo.head()?.peel_to_commit()?;
+
+    let mut index = Index::new()?;
+    index.read_tree(&parent.tree()?)?;
+    for change in changes {
+        let id = repo.blob(change.after.as_bytes())?;
+        let mut entry = index
+            .get_path(&change.path, 0)
+            .unwrap_or_else(|| new_index_entry(&change.path, id));
+        entry.id = id;
+        entry.file_size = change.after.len() as u32;
+        index.add(&entry)?;
+    }
+    let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+
+    let signature = repo.signature()?;
+    let commit = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &[&parent],
+    )?;
+    log::info!("Committed the release as `{commit}`");
+
+    let object = repo.find_object(commit, None)?;
+    if annotated {
+        repo.tag(tag, &object, &signature, message, false)?;
+    } else {
+        repo.tag_lightweight(tag, &object, false)?;
+    }
+    log::info!("Created the tag `{tag}`");
+
+    // Stage the written files so the index matches the release commit.
+    let mut index = repo.index()?;
+    for change in changes {
+        index.add_path(&change.path)?;
+    }
+    index.write()?;
+
+    Ok(())
+}
+
+/// An index entry for a regular file not yet tracked in the repository.
+fn new_index_entry(path: &Path, id: Oid) -> IndexEntry {
+    let path = path.to_string_lossy().replace('\\', "/");
+    IndexEntry {
+        ctime: IndexTime::new(0, 0),
+        mtime: IndexTime::new(0, 0),
+        dev: 0,
+        ino: 0,
+        mode: 0o100644,
+        uid: 0,
+        gid: 0,
+        file_size: 0,
+        id,
+        flags: path.len().min(0xfff) as u16,
+        flags_extended: 0,
+        path: path.into_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+    use toml_edit::DocumentMut;
+
+    use super::{inherits_version, package_version, set_lock_versions, ApplyConfig, FileChange};
+
+    #[test]
+    fn test_message_placeholders() {
+        let config = ApplyConfig::new().set_message("release {tag} ({version})");
+
+        assert_eq!("release v1.2.0 (1.2.0)", config.message("1.2.0", "v1.2.0"));
+        assert_eq!(
+            "chore: release 1.2.0",
+            ApplyConfig::new().message("1.2.0", "v1.2.0")
+        );
+    }
+
+    #[rstest]
+    #[case::own(
+        "[package]\nname = \"one\"\nversion = \"0.1.0\"\n",
+        Some("0.1.0"),
+        false
+    )]
+    #[case::inherited("[package]\nname = \"one\"\nversion.workspace = true\n", None, true)]
+    #[case::virtual_manifest(
+        "[workspace]\n[workspace.package]\nversion = \"0.1.0\"\n",
+        None,
+        false
+    )]
+    fn test_version_source(
+        #[case] content: &str,
+        #[case] expected_version: Option<&str>,
+        #[case] expected_inherits: bool,
+    ) {
+        let manifest: DocumentMut = content.parse().unwrap();
+
+        assert_eq!(
+            expected_version.map(String::from),
+            package_version(&manifest)
+        );
+        assert_eq!(expected_inherits, inherits_version(&manifest));
+    }
+
+    #[test]
+    fn test_set_lock_versions() {
+        let lock = r#"version = 4
+
+[[package]]
+name = "one"
+version = "0.1.0"
+
+[[package]]
+name = "other"
+version = "0.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+        let updated =
+            set_lock_versions(lock, &[("one".to_string(), "0.1.0".to_string())], "0.2.0").unwrap();
+
+        assert_eq!(
+            lock.replacen("version = \"0.1.0\"", "version = \"0.2.0\"", 1),
+            updated
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let change = FileChange {
+            path: PathBuf::from("Cargo.toml"),
+            before: "[package]\nname = \"one\"\nversion = \"0.1.0\"\n".to_string(),
+            after: "[package]\nname = \"one\"\nversion = \"0.2.0\"\n".to_string(),
+        };
+
+        assert_eq!(
+            "--- a/Cargo.toml\n+++ b/Cargo.toml\n@@ -3 +3 @@\n-version = \"0.1.0\"\n+version = \"0.2.0\"\n",
+            change.diff()
+        );
+    }
+}
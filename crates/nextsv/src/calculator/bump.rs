@@ -2,7 +2,7 @@ use std::fmt;
 
 use crate::{version::PreReleaseType, ForceBump};
 
-use super::{ConventionalCommits, Route};
+use super::{ConventionalCommits, Route, TopType};
 
 /// Bump at which the next increment will be made
 ///
@@ -108,10 +108,16 @@ impl Bump {
             return bump;
         };
 
+        // Only commits with types that do not require a release; no change.
+        if conventional.top_type == TopType::None {
+            log::info!("No commits found with a type that requires a release.");
+            return bump;
+        };
+
         log::debug!("Starting calculation with bump level of {bump}");
         match route {
             Route::NonProd => {
-                bump = if conventional.breaking {
+                bump = if conventional.breaking || conventional.top_type == TopType::Breaking {
                     // Breaking change found in commits
                     log::info!("Non production breaking change found.");
                     Bump::Minor
@@ -129,12 +135,12 @@ impl Bump {
             }
             Route::Prod => {
                 log::debug!("Calculating the prod version change bump");
-                bump = if conventional.breaking {
+                bump = if conventional.breaking || conventional.top_type == TopType::Breaking {
                     log::debug!("breaking change found");
                     Bump::Major
-                } else if 0 < *conventional.counts.get("feat").unwrap_or(&0_u32) {
+                } else if conventional.top_type == TopType::Feature {
                     log::debug!(
-                        "{} feature commit(s) found requiring increment of minor number",
+                        "{} feature level commit(s) found requiring increment of minor number",
                         conventional.counts.get("feat").unwrap_or(&0_u32)
                     );
                     Bump::Minor
@@ -199,6 +205,17 @@ mod test {
         }
     }
 
+    #[fixture]
+    fn no_release() -> ConventionalCommits {
+        ConventionalCommits {
+            commits: vec!["docs: Updated the README".to_string()],
+            counts: hash_map! {"docs".to_string() => 1},
+            breaking: false,
+            top_type: TopType::None,
+            ..Default::default()
+        }
+    }
+
     #[fixture]
     fn fix() -> ConventionalCommits {
         ConventionalCommits {
@@ -243,7 +260,8 @@ mod test {
             Route::Prod
         )]
         route: Route,
-        #[values(other(), fix(), feature(), breaking())] conventional: ConventionalCommits,
+        #[values(no_release(), other(), fix(), feature(), breaking())]
+        conventional: ConventionalCommits,
     ) {
         println!("Route: {route}");
         println!("Conventional: {conventional:?}");
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use git2::{Commit as GitCommit, Repository};
 
-use crate::Error;
+use crate::{Error, ParsedCommit};
 
 #[derive(Clone)]
 pub struct Commit<'a> {
@@ -23,6 +23,15 @@ impl<'a> Commit<'a> {
         Ok(self.git_commit.summary()?.unwrap_or_default().to_string())
     }
 
+    /// Parse the full message of the commit, or the summary if the message
+    /// cannot be read.
+    pub(crate) fn parse(&self) -> Result<ParsedCommit, Error> {
+        match self.git_commit.message() {
+            Ok(message) => ParsedCommit::parse(message),
+            Err(_) => ParsedCommit::parse(&self.message()?),
+        }
+    }
+
     // pub(crate) fn hash(&self) -> String {
     //     self.git_commit.id().to_string()
     // }
@@ -0,0 +1,129 @@
+use std::fmt;
+
+use super::TopType;
+
+/// Why a commit found while walking back to the version tag was not counted
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum SkipReason {
+    /// Merge commits are not analysed
+    Merge,
+    /// The commit does not change files in the subdir
+    OutsideSubdir(String),
+    /// The commit only changes excluded paths
+    Excluded,
+    /// The scope of the commit is not selected
+    Scope(Option<String>),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Merge => write!(f, "merge commit"),
+            SkipReason::OutsideSubdir(subdir) => write!(f, "outside subdir `{subdir}`"),
+            SkipReason::Excluded => write!(f, "only excluded paths changed"),
+            SkipReason::Scope(Some(scope)) => write!(f, "scope `{scope}` not selected"),
+            SkipReason::Scope(None) => write!(f, "no scope selected"),
+        }
+    }
+}
+
+/// Record of a commit considered in the calculation, used to explain the result
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub(crate) struct CommitRecord {
+    /// Abbreviated commit id
+    pub(crate) id: String,
+    /// First line of the commit message
+    pub(crate) summary: String,
+    /// The conventional commit type, if the commit is conventional
+    pub(crate) commit_type: Option<String>,
+    /// The level contributed by the commit
+    pub(crate) level: TopType,
+    /// Set if the commit was not counted
+    pub(crate) skipped: Option<SkipReason>,
+}
+
+impl fmt::Display for CommitRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = if self.skipped.is_some() {
+            "skipped"
+        } else {
+            self.level.name()
+        };
+        write!(f, "{:<7} {:<8} {}", self.id, level, self.summary)?;
+        if let Some(reason) = &self.skipped {
+            write!(f, " ({reason})")?;
+        } else if self.commit_type.is_none() {
+            write!(f, " (non-conventional)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{CommitRecord, SkipReason};
+    use crate::calculator::TopType;
+
+    #[rstest]
+    #[case::conventional(Some("feat"), TopType::Feature, None, "abc1234 feature  feat: new api")]
+    #[case::breaking(
+        Some("feat"),
+        TopType::Breaking,
+        None,
+        "abc1234 breaking feat: new api"
+    )]
+    #[case::non_conventional(
+        None,
+        TopType::Other,
+        None,
+        "abc1234 other    feat: new api (non-conventional)"
+    )]
+    #[case::merge(
+        None,
+        TopType::None,
+        Some(SkipReason::Merge),
+        "abc1234 skipped  feat: new api (merge commit)"
+    )]
+    #[case::outside_subdir(
+        Some("feat"),
+        TopType::None,
+        Some(SkipReason::OutsideSubdir("crates/one".to_string())),
+        "abc1234 skipped  feat: new api (outside subdir `crates/one`)"
+    )]
+    #[case::excluded(
+        Some("docs"),
+        TopType::None,
+        Some(SkipReason::Excluded),
+        "abc1234 skipped  feat: new api (only excluded paths changed)"
+    )]
+    #[case::scope(
+        Some("feat"),
+        TopType::None,
+        Some(SkipReason::Scope(Some("api".to_string()))),
+        "abc1234 skipped  feat: new api (scope `api` not selected)"
+    )]
+    #[case::no_scope(
+        Some("feat"),
+        TopType::None,
+        Some(SkipReason::Scope(None)),
+        "abc1234 skipped  feat: new api (no scope selected)"
+    )]
+    fn test_display(
+        #[case] commit_type: Option<&str>,
+        #[case] level: TopType,
+        #[case] skipped: Option<SkipReason>,
+        #[case] expected: &str,
+    ) {
+        let record = CommitRecord {
+            id: "abc1234".to_string(),
+            summary: "feat: new api".to_string(),
+            commit_type: commit_type.map(String::from),
+            level,
+            skipped,
+        };
+
+        assert_eq!(expected, record.to_string());
+    }
+}
@@ -1,6 +1,22 @@
-use std::{collections::HashSet, ffi::OsString};
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 
-use crate::{Calculator, Error, ForceBump, Hierarchy};
+use cargo_toml::Manifest;
+use git2::Repository;
+
+use crate::{
+    version::{TagTemplate, VersionTag},
+    Calculator, ConfigFile, Error, ForceBump, Hierarchy, PackageConfig, TagList, TagSelection,
+    VersionHistory, WorkspaceCalculator,
+};
+
+use super::{
+    path_filter::{PackagePaths, PathFilter, PathGlobs},
+    scope_filter::ScopeFilter,
+};
 
 /// Captures the user configuration set for the bump and version number
 /// calculation
@@ -23,6 +39,8 @@ pub struct CalculatorConfig {
     pub(crate) report_bump: bool,
     /// Report the calculated next version number  [default: false]
     pub(crate) report_number: bool,
+    /// Report the name of the tag for the next version  [default: false]
+    pub(crate) report_tag: bool,
     /// Optional: Files that must be updated before making the release
     pub(crate) files: HashSet<OsString>,
     /// Level at which file updates should be enforced [default: feature]
@@ -30,6 +48,40 @@ pub struct CalculatorConfig {
     /// Threshold level at which release should proceed [default: Other]
     /// Returns Level::None if the threshold is not met.
     pub(crate) threshold: Hierarchy,
+    /// Optional: Levels assigned to commit types, overriding the default mapping
+    pub(crate) type_levels: HashMap<String, Hierarchy>,
+    /// Optional: Level inherited by a workspace package when a dependency is released [default: fix]
+    pub(crate) dependency_level: Option<Hierarchy>,
+    /// Optional: Workspace dependencies re-exported by each package
+    pub(crate) re_exports: HashMap<String, HashSet<String>>,
+    /// Release all workspace packages together with a shared version [default: false]
+    pub(crate) lockstep: bool,
+    /// Optional: Globs for the paths considered in the calculation
+    pub(crate) include_paths: Vec<String>,
+    /// Optional: Globs for the paths not considered in the calculation
+    pub(crate) exclude_paths: Vec<String>,
+    /// Optional: Commit scopes considered in the calculation
+    pub(crate) include_scopes: Vec<String>,
+    /// Optional: Commit scopes not considered in the calculation
+    pub(crate) exclude_scopes: Vec<String>,
+    /// Optional: Globs adjusting the files that count for each package
+    pub(crate) package_paths: HashMap<String, PackagePaths>,
+    /// Do not count changes to manifest and lock files in the repository root [default: false]
+    pub(crate) ignore_root_files: bool,
+    /// Version tag taken as the current version [default: highest]
+    pub(crate) tag_selection: TagSelection,
+    /// Optional: Revision to count the commits from [default: the current version tag]
+    pub(crate) from: Option<String>,
+    /// Optional: Revision to count the commits to [default: HEAD]
+    pub(crate) to: Option<String>,
+    /// Optional: Path in the git repository to calculate for [default: current directory]
+    pub(crate) repository_path: Option<PathBuf>,
+    /// Optional: Version to release first when no version tag is found [default: the manifest version or 0.1.0]
+    pub(crate) initial_version: Option<String>,
+    /// Only consider annotated version tags [default: false]
+    pub(crate) annotated_only: bool,
+    /// Optional: Template for the names of the version tags, e.g. `{package}@{version}` [default: the prefix]
+    pub(crate) tag_template: Option<String>,
 }
 
 impl CalculatorConfig {
@@ -129,6 +181,284 @@ impl CalculatorConfig {
         self
     }
 
+    /// Set how the version tag taken as the current version is selected.
+    ///
+    /// By default the highest version tag reachable from the starting commit is
+    /// selected. See [`TagSelection`] for the alternatives.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::{CalculatorConfig, TagSelection};
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .set_tag_selection(TagSelection::Nearest)
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_tag_selection(mut self, selection: TagSelection) -> Self {
+        self.tag_selection = selection;
+        self
+    }
+
+    /// Set the flag to consider only annotated version tags.
+    ///
+    /// Lightweight tags, such as those created by `git tag v1.2.3` without a
+    /// message, are then ignored when finding the current version.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .set_annotated_only(true)
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_annotated_only(mut self, annotated_only: bool) -> Self {
+        self.annotated_only = annotated_only;
+        self
+    }
+
+    /// Set the revision from which the commits are counted.
+    ///
+    /// The revision may be any revspec understood by git, such as a tag, a
+    /// branch or a commit id. Commits reachable from the revision are not
+    /// counted. If the revision is a version tag it is used as the current
+    /// version, otherwise the current version is the latest version tag.
+    ///
+    /// # Example
+    ///
+    /// Calculate the release for the commits on a branch that are not on `main`.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .set_from(Some("main"))
+    ///         .set_to(Some("feature/parser"))
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_from(mut self, from: Option<&str>) -> Self {
+        self.from = from.map(String::from);
+        self
+    }
+
+    /// Set the revision to which the commits are counted, in place of HEAD.
+    ///
+    /// See [`CalculatorConfig::set_from`].
+    pub fn set_to(mut self, to: Option<&str>) -> Self {
+        self.to = to.map(String::from);
+        self
+    }
+
+    /// Set the path to the git repository.
+    ///
+    /// The path may be any directory in the repository; the repository is found
+    /// by searching the directory and its parents. Manifests are read relative to
+    /// the working directory of the repository. By default the search starts in
+    /// the current directory.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .set_repository_path(Path::new("../checkouts/project"))
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_repository_path(mut self, path: &Path) -> Self {
+        self.repository_path = Some(path.to_path_buf());
+        self
+    }
+
+    /// Set the version to release first when no version tag is found.
+    ///
+    /// Without a version tag all the commits from the root commit are counted
+    /// and the first version is reported as the next version. If the initial
+    /// version is not set the first version is only reported for a repository
+    /// without any tags, using the version in the package manifest, or `0.1.0`
+    /// if there is no manifest.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .set_initial_version(Some("0.0.1"))
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_initial_version(mut self, version: Option<&str>) -> Self {
+        self.initial_version = version.map(String::from);
+        self
+    }
+
+    /// Set the template for the names of the version tags.
+    ///
+    /// The template is used both to find the current version tag and to name
+    /// the tag of the next version. It must contain `{version}` once and may
+    /// contain `{package}` and `{path}`, which are replaced by the name of the
+    /// package and its directory relative to the root of the repository. For
+    /// example `{package}@{version}` or `{path}/v{version}`.
+    ///
+    /// The template replaces the prefix when it is set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_package(Some("crate2"))
+    ///         .set_tag_template(Some("{package}@{version}"))
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_tag_template(mut self, template: Option<&str>) -> Self {
+        self.tag_template = template.map(String::from);
+        self
+    }
+
+    /// The template of the version tags of the package in the directory
+    /// `path`, relative to the root of the repository.
+    ///
+    /// Without a tag template the tags are named by the prefix, or by
+    /// `<package>-v` for a package.
+    pub(crate) fn tag_template(
+        &self,
+        package: Option<&str>,
+        path: &str,
+    ) -> Result<TagTemplate, Error> {
+        match &self.tag_template {
+            Some(template) => TagTemplate::new(template, package.unwrap_or_default(), path),
+            None => Ok(TagTemplate::from_prefix(
+                package.unwrap_or_default(),
+                self.prefix.as_str(),
+            )),
+        }
+    }
+
+    /// The version tag for the first version when no version tag is found.
+    ///
+    /// The version is the initial version, else the version in the manifest
+    /// in `dir`, else `0.1.0`. Without an initial version the first version is
+    /// only calculated if the repository has no tags at all, so that a tag
+    /// template or prefix that matches none of the tags is reported as
+    /// [`Error::NoVersionTag`]. Forcing a bump always requires a version tag.
+    pub(crate) fn first_version(
+        &self,
+        repo: &Repository,
+        dir: &Path,
+        template: &TagTemplate,
+    ) -> Result<VersionTag, Error> {
+        if self.force.is_some() {
+            log::error!("A bump cannot be forced without a version tag");
+            return Err(Error::NoVersionTag);
+        }
+        let version = match &self.initial_version {
+            Some(version) => version.clone(),
+            None if repo.tag_names(None)?.is_empty() => {
+                manifest_version(dir)?.unwrap_or_else(|| String::from("0.1.0"))
+            }
+            None => {
+                log::error!("No version tag found and no initial version set");
+                return Err(Error::NoVersionTag);
+            }
+        };
+        self.first_version_tag(repo, &version, template)
+    }
+
+    /// The version tag for the first version of a workspace member with no
+    /// version tag.
+    ///
+    /// The version is the initial version, else the version in the member's
+    /// manifest in `dir`, else `0.1.0`. Unlike [`Self::first_version`] the
+    /// other tags in the repository are not considered, as they belong to the
+    /// members that have already been released.
+    pub(crate) fn member_first_version(
+        &self,
+        repo: &Repository,
+        dir: &Path,
+        template: &TagTemplate,
+    ) -> Result<VersionTag, Error> {
+        let version = match &self.initial_version {
+            Some(version) => version.clone(),
+            None => manifest_version(dir)?.unwrap_or_else(|| String::from("0.1.0")),
+        };
+        self.first_version_tag(repo, &version, template)
+    }
+
+    /// The version tag for `version`, provided the tag does not exist yet.
+    fn first_version_tag(
+        &self,
+        repo: &Repository,
+        version: &str,
+        template: &TagTemplate,
+    ) -> Result<VersionTag, Error> {
+        log::debug!("First version is `{version}`");
+
+        let first_version = VersionTag::first(version, template)?;
+        if repo.find_reference(&first_version.to_string()).is_ok() {
+            log::error!(
+                "The tag `{}` for the first version already exists",
+                first_version.tag_name()
+            );
+            return Err(Error::FirstVersionTagExists(first_version.tag_name()));
+        }
+
+        Ok(first_version)
+    }
+
+    /// The directory from which the repository and the workspace are searched.
+    pub(crate) fn repository_dir(&self) -> &Path {
+        self.repository_path
+            .as_deref()
+            .unwrap_or_else(|| Path::new("."))
+    }
+
+    /// Open the repository containing the repository path, returning it with
+    /// the canonical path of its working directory.
+    pub(crate) fn open_repository(&self) -> Result<(Repository, PathBuf), Error> {
+        let repo = Repository::discover(self.repository_dir())?;
+        let Some(workdir) = repo.workdir() else {
+            return Err(Error::BareRepository(repo.path().display().to_string()));
+        };
+        let workdir = workdir.canonicalize()?;
+        log::debug!("Using the repository at `{}`", workdir.display());
+
+        Ok((repo, workdir))
+    }
+
     /// Set the flag indicating if the bump should be reported by the [`Calculator::report`] method.
     /// - `true` indicates that the value should be reported
     /// - `false` indicates that the value should not be reported
@@ -145,6 +475,17 @@ impl CalculatorConfig {
         self
     }
 
+    /// Set the flag indicating if the name of the tag for the next version should be reported by the [`Calculator::report`] method.
+    /// - `true` indicates that the value should be reported
+    /// - `false` indicates that the value should not be reported
+    ///
+    /// The tag name is formed from the prefix, or the tag template, of the
+    /// current version tag, e.g. `nextsv-v0.19.36` for the package `nextsv`.
+    pub fn set_tag_report(mut self, report_tag: bool) -> Self {
+        self.report_tag = report_tag;
+        self
+    }
+
     /// Force the bump result ignoring the bump that would be indicated by an analysis of conventional commits.
     ///
     /// The forced bump result will be reported and the next version will be calculated based on the forced bump level.
@@ -327,6 +668,458 @@ impl CalculatorConfig {
         self
     }
 
+    /// Assign a commit type to a level in the [`Hierarchy`].
+    ///
+    /// By default `feat` is a `Feature`, `fix` and `revert` are a `Fix` and all other
+    /// types are `Other`. Types mapped to `Hierarchy::None` do not require a release
+    /// and do not contribute to the calculated bump. Commit types are matched ignoring case.
+    ///
+    /// # Example
+    ///
+    /// Treat `perf` commits as features and do not release for `docs` commits.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::{CalculatorConfig, Hierarchy};
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .set_type_level("perf", Hierarchy::Feature)
+    ///         .set_type_level("docs", Hierarchy::None)
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_type_level(mut self, commit_type: &str, level: Hierarchy) -> Self {
+        self.type_levels.insert(commit_type.to_lowercase(), level);
+        self
+    }
+
+    /// Add commit types that do not require a release.
+    ///
+    /// Commits of these types contribute nothing to the calculated bump, so if only
+    /// commits of these types have been made since the last version tag the bump is
+    /// reported as `none`. This is equivalent to calling [`CalculatorConfig::set_type_level`]
+    /// with `Hierarchy::None` for each type. Breaking changes are still reported.
+    ///
+    /// # Example
+    ///
+    /// Do not release when only documentation or CI changes have been made.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .add_no_release_types(vec!["docs".to_string(), "ci".to_string()])
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ```console
+    /// none
+    /// ```
+    pub fn add_no_release_types(mut self, commit_types: Vec<String>) -> Self {
+        for commit_type in commit_types {
+            self = self.set_type_level(&commit_type, Hierarchy::None);
+        }
+
+        self
+    }
+
+    /// Set the level inherited by a workspace package when one of its workspace
+    /// dependencies is released.
+    ///
+    /// Used by [`CalculatorConfig::build_workspace`]. The default level is `Fix`,
+    /// giving a patch bump to the dependent package. Set `Hierarchy::None` to
+    /// turn off propagation of bumps to dependent packages.
+    pub fn set_dependency_level(mut self, level: Hierarchy) -> Self {
+        self.dependency_level = Some(level);
+        self
+    }
+
+    /// Record that a workspace package re-exports the types of a workspace dependency.
+    ///
+    /// Used by [`CalculatorConfig::build_workspace`]. A breaking change released by
+    /// the dependency is inherited as a breaking change by the package.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::{CalculatorConfig, Hierarchy};
+    ///     let workspace = CalculatorConfig::new()
+    ///         .set_dependency_level(Hierarchy::Fix)
+    ///         .add_re_export("app", "core")
+    ///         .build_workspace()?;
+    ///
+    ///     println!("{}", workspace.report());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_re_export(mut self, package: &str, dependency: &str) -> Self {
+        self.re_exports
+            .entry(package.to_string())
+            .or_default()
+            .insert(dependency.to_string());
+        self
+    }
+
+    /// Set the lockstep flag to release every package of the workspace together.
+    ///
+    /// Used by [`CalculatorConfig::build_workspace`]. In lockstep mode the commits
+    /// changing any workspace member are collected since the shared version tag,
+    /// identified by the prefix, and one next version is calculated that applies
+    /// to every package.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let workspace = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .set_lockstep(true)
+    ///         .build_workspace()?;
+    ///
+    ///     println!("{}", workspace.report());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_lockstep(mut self, lockstep: bool) -> Self {
+        self.lockstep = lockstep;
+        self
+    }
+
+    /// Add globs for the paths considered in the calculation.
+    ///
+    /// Once include globs are set, commits that do not change a path matching
+    /// one of them are skipped. Globs containing a `/` are matched against the
+    /// path from the root of the repository, other globs are matched against the
+    /// file name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .add_include_paths(vec!["src/**".to_string(), "Cargo.toml".to_string()])
+    ///         .add_exclude_paths(vec!["docs/**".to_string(), "*.md".to_string()])
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_include_paths(mut self, globs: Vec<String>) -> Self {
+        self.include_paths.extend(globs);
+        self
+    }
+
+    /// Add globs for the paths not considered in the calculation.
+    ///
+    /// Commits that only change excluded paths are skipped. See
+    /// [`CalculatorConfig::add_include_paths`] for the matching of globs.
+    pub fn add_exclude_paths(mut self, globs: Vec<String>) -> Self {
+        self.exclude_paths.extend(globs);
+        self
+    }
+
+    /// Compile the include and exclude globs selecting the paths considered.
+    pub(crate) fn path_globs(&self) -> Result<PathGlobs, Error> {
+        PathGlobs::new(&self.include_paths, &self.exclude_paths)
+    }
+
+    /// Add commit scopes considered in the calculation.
+    ///
+    /// Once include scopes are set, commits without one of the scopes are
+    /// skipped. Scopes are compared in lowercase and a scope such as
+    /// `(parser, lexer)` is treated as the two scopes `parser` and `lexer`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .add_exclude_scopes(vec!["docs".to_string(), "ci".to_string()])
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_include_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.include_scopes.extend(scopes);
+        self
+    }
+
+    /// Add commit scopes not considered in the calculation.
+    ///
+    /// Commits where every scope is excluded are skipped.
+    pub fn add_exclude_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.exclude_scopes.extend(scopes);
+        self
+    }
+
+    /// Create the filter selecting the commits considered by their scopes.
+    pub(crate) fn scope_filter(&self) -> ScopeFilter {
+        ScopeFilter::new(&self.include_scopes, &self.exclude_scopes)
+    }
+
+    /// Map a commit scope to a package.
+    ///
+    /// A commit with the scope counts as a change to the package even if it
+    /// does not change files of the package.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let workspace = CalculatorConfig::new()
+    ///         .add_package_scope("parser", "grammar")
+    ///         .build_workspace()?;
+    ///
+    ///     println!("{}", workspace.report());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_package_scope(mut self, package: &str, scope: &str) -> Self {
+        self.package_paths
+            .entry(package.to_string())
+            .or_default()
+            .scopes
+            .push(scope.to_string());
+        self
+    }
+
+    /// Add a glob for files outside the package directory that count as changes
+    /// to the package.
+    ///
+    /// Globs containing a `/` are matched against the path from the root of the
+    /// repository, other globs are matched against the file name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_package(Some("core"))
+    ///         .add_package_include("core", "proto/**")
+    ///         .add_package_exclude("core", "CHANGELOG.md")
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_package_include(mut self, package: &str, glob: &str) -> Self {
+        self.package_paths
+            .entry(package.to_string())
+            .or_default()
+            .include
+            .push(glob.to_string());
+        self
+    }
+
+    /// Add a glob for files that do not count as changes to the package, even
+    /// if they are in the package directory.
+    ///
+    /// See [`CalculatorConfig::add_package_include`] for the matching of globs.
+    pub fn add_package_exclude(mut self, package: &str, glob: &str) -> Self {
+        self.package_paths
+            .entry(package.to_string())
+            .or_default()
+            .exclude
+            .push(glob.to_string());
+        self
+    }
+
+    /// Set the flag to stop changes to manifest and lock files in the root of
+    /// the repository counting as changes to every package.
+    pub fn set_ignore_root_files(mut self, ignore_root_files: bool) -> Self {
+        self.ignore_root_files = ignore_root_files;
+        self
+    }
+
+    /// Create the filter deciding which changed files count for the package
+    /// in the directory `member`.
+    pub(crate) fn path_filter(
+        &self,
+        package: Option<&str>,
+        member: &str,
+    ) -> Result<PathFilter, Error> {
+        PathFilter::new(
+            member,
+            package.and_then(|package| self.package_paths.get(package)),
+            !self.ignore_root_files,
+        )
+    }
+
+    /// Apply the settings loaded from the project configuration files.
+    ///
+    /// Settings that are not present in the [`ConfigFile`] are left unchanged so
+    /// the config file can be applied over defaults and the command line
+    /// arguments applied afterwards to override it.
+    ///
+    /// # Example
+    ///
+    /// See [`ConfigFile::load`].
+    pub fn apply_config_file(mut self, config_file: &ConfigFile) -> Self {
+        if let Some(prefix) = &config_file.prefix {
+            self = self.set_prefix(prefix);
+        }
+        if config_file.subdir.is_some() {
+            self = self.set_subdir(config_file.subdir.as_deref());
+        }
+        if config_file.package.is_some() {
+            self = self.set_package(config_file.package.as_deref());
+        }
+        if let Some(files) = &config_file.required_files {
+            self = self.add_required_files(files.iter().map(OsString::from).collect());
+        }
+        if let Some(enforce) = &config_file.enforce_level {
+            self = self.set_required_enforcement(enforce.clone());
+        }
+        if let Some(threshold) = &config_file.check {
+            self = self.set_reporting_threshold(threshold.clone());
+        }
+        for (commit_type, level) in &config_file.type_levels {
+            self = self.set_type_level(commit_type, level.clone());
+        }
+        if let Some(no_release) = &config_file.no_release {
+            self = self.add_no_release_types(no_release.clone());
+        }
+        if let Some(level) = &config_file.dependency_level {
+            self = self.set_dependency_level(level.clone());
+        }
+        if let Some(lockstep) = config_file.lockstep {
+            self = self.set_lockstep(lockstep);
+        }
+        for (package, dependencies) in &config_file.re_exports {
+            for dependency in dependencies {
+                self = self.add_re_export(package, dependency);
+            }
+        }
+        if let Some(globs) = &config_file.include_paths {
+            self = self.add_include_paths(globs.clone());
+        }
+        if let Some(globs) = &config_file.exclude_paths {
+            self = self.add_exclude_paths(globs.clone());
+        }
+        if let Some(scopes) = &config_file.include_scopes {
+            self = self.add_include_scopes(scopes.clone());
+        }
+        if let Some(scopes) = &config_file.exclude_scopes {
+            self = self.add_exclude_scopes(scopes.clone());
+        }
+        for (package, paths) in &config_file.packages {
+            for glob in &paths.include {
+                self = self.add_package_include(package, glob);
+            }
+            for glob in &paths.exclude {
+                self = self.add_package_exclude(package, glob);
+            }
+            for scope in &paths.scopes {
+                self = self.add_package_scope(package, scope);
+            }
+        }
+        if let Some(selection) = config_file.tag_selection {
+            self = self.set_tag_selection(selection);
+        }
+        if let Some(ignore_root_files) = config_file.ignore_root_files {
+            self = self.set_ignore_root_files(ignore_root_files);
+        }
+        if let Some(annotated_only) = config_file.annotated_only {
+            self = self.set_annotated_only(annotated_only);
+        }
+        if config_file.initial_version.is_some() {
+            self = self.set_initial_version(config_file.initial_version.as_deref());
+        }
+        if config_file.tag_template.is_some() {
+            self = self.set_tag_template(config_file.tag_template.as_deref());
+        }
+
+        self
+    }
+
+    /// Report the effective settings in the layout of the [`ConfigFile`].
+    pub fn config_file(&self) -> ConfigFile {
+        let mut required_files: Vec<String> = self
+            .files
+            .iter()
+            .map(|file| file.to_string_lossy().to_string())
+            .collect();
+        required_files.sort();
+
+        // Types that do not require a release are reported as the no-release list.
+        let (no_release, type_levels): (Vec<_>, Vec<_>) = self
+            .type_levels
+            .clone()
+            .into_iter()
+            .partition(|(_, level)| *level == Hierarchy::None);
+        let mut no_release: Vec<String> = no_release
+            .into_iter()
+            .map(|(commit_type, _)| commit_type)
+            .collect();
+        no_release.sort();
+
+        ConfigFile {
+            prefix: Some(self.prefix.clone()),
+            subdir: self.subdir.clone(),
+            package: self.package.clone(),
+            required_files: (!required_files.is_empty()).then_some(required_files),
+            enforce_level: Some(self.enforce.clone()),
+            check: Some(self.threshold.clone()),
+            type_levels: type_levels.into_iter().collect(),
+            no_release: (!no_release.is_empty()).then_some(no_release),
+            dependency_level: self.dependency_level.clone(),
+            lockstep: self.lockstep.then_some(true),
+            re_exports: self
+                .re_exports
+                .iter()
+                .map(|(package, dependencies)| {
+                    let mut dependencies: Vec<String> = dependencies.iter().cloned().collect();
+                    dependencies.sort();
+                    (package.clone(), dependencies)
+                })
+                .collect(),
+            include_paths: (!self.include_paths.is_empty()).then(|| self.include_paths.clone()),
+            exclude_paths: (!self.exclude_paths.is_empty()).then(|| self.exclude_paths.clone()),
+            include_scopes: (!self.include_scopes.is_empty()).then(|| self.include_scopes.clone()),
+            exclude_scopes: (!self.exclude_scopes.is_empty()).then(|| self.exclude_scopes.clone()),
+            packages: self
+                .package_paths
+                .iter()
+                .map(|(package, paths)| {
+                    (
+                        package.clone(),
+                        PackageConfig {
+                            include: paths.include.clone(),
+                            exclude: paths.exclude.clone(),
+                            scopes: paths.scopes.clone(),
+                        },
+                    )
+                })
+                .collect(),
+            ignore_root_files: self.ignore_root_files.then_some(true),
+            tag_selection: (self.tag_selection != TagSelection::default())
+                .then_some(self.tag_selection),
+            initial_version: self.initial_version.clone(),
+            annotated_only: self.annotated_only.then_some(true),
+            tag_template: self.tag_template.clone(),
+        }
+    }
+
     /// Set the flag to force the first version to be calculated as 1.0.0 to true. This is
     /// useful in combination with a pre-release to create a pre-release for the for the
     /// first production release.
@@ -342,18 +1135,115 @@ impl CalculatorConfig {
         log::debug!("Config at build: {self:?}");
         Calculator::execute(self)
     }
+
+    /// Executes the calculator for every package in the workspace with the
+    /// `CalculatorConfig` returning a completed [`WorkspaceCalculator`] or an [`Error`].
+    ///
+    /// The commits are walked once and attributed to each package by the files
+    /// changed in the package directory.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let workspace = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .build_workspace()?;
+    ///
+    ///     println!("{}", workspace.report());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_workspace(self) -> Result<WorkspaceCalculator, Error> {
+        log::debug!("Config at workspace build: {self:?}");
+        WorkspaceCalculator::execute(self)
+    }
+
+    /// Check every tag of the repository as a version tag with the
+    /// `CalculatorConfig` returning a [`TagList`] or an [`Error`].
+    ///
+    /// The list reports why each tag was accepted or rejected and which tag
+    /// is taken as the current version, to diagnose the tags that are found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let tags = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .build_tag_list()?;
+    ///
+    ///     println!("{}", tags.report());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_tag_list(self) -> Result<TagList, Error> {
+        log::debug!("Config at tag list build: {self:?}");
+        TagList::execute(self)
+    }
+
+    /// List the version history with the `CalculatorConfig` returning a
+    /// [`VersionHistory`] or an [`Error`].
+    ///
+    /// Every version tag of the prefix, or package, is listed in precedence
+    /// order with the tagged commit, the date and the level of change found
+    /// in the commits since the previous version.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::CalculatorConfig;
+    ///     let history = CalculatorConfig::new()
+    ///         .set_package(Some("crate2"))
+    ///         .build_history()?;
+    ///
+    ///     println!("{}", history.report());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_history(self) -> Result<VersionHistory, Error> {
+        log::debug!("Config at history build: {self:?}");
+        VersionHistory::execute(self)
+    }
+}
+
+/// The version of the package, or of the workspace, in the manifest in `dir`.
+///
+/// A package version inherited with `version.workspace = true` is resolved
+/// from the workspace root when the manifest is read.
+fn manifest_version(dir: &Path) -> Result<Option<String>, Error> {
+    let manifest_path = dir.join("Cargo.toml");
+    if !manifest_path.is_file() {
+        log::debug!("No manifest found in `{}`", dir.display());
+        return Ok(None);
+    }
+    let manifest = Manifest::from_path(&manifest_path)?;
+
+    let version = match (manifest.package, manifest.workspace) {
+        (Some(package), _) => Some(package.version.get()?.to_string()),
+        (None, Some(workspace)) => workspace
+            .package
+            .and_then(|package| package.version)
+            .map(|version| version.to_string()),
+        (None, None) => None,
+    };
+
+    Ok(version)
 }
 
 #[cfg(test)]
 mod test {
     use std::ffi::OsString;
 
-    use map_macro::hash_set;
+    use map_macro::{hash_map, hash_set};
     use rstest::{fixture, rstest};
     use std::collections::HashSet;
 
     use super::CalculatorConfig;
-    use crate::{ForceBump, Hierarchy};
+    use crate::{ConfigFile, ForceBump, Hierarchy, TagSelection};
 
     fn default_calculator_config() -> CalculatorConfig {
         CalculatorConfig {
@@ -364,9 +1254,27 @@ mod test {
             force_first_version: false,
             report_bump: true,
             report_number: false,
+            report_tag: false,
             files: hash_set![],
             enforce: Hierarchy::Other,
             threshold: Hierarchy::Other,
+            type_levels: hash_map![],
+            dependency_level: None,
+            re_exports: hash_map![],
+            lockstep: false,
+            include_paths: vec![],
+            exclude_paths: vec![],
+            include_scopes: vec![],
+            exclude_scopes: vec![],
+            package_paths: hash_map![],
+            ignore_root_files: false,
+            tag_selection: TagSelection::Highest,
+            from: None,
+            to: None,
+            repository_path: None,
+            initial_version: None,
+            annotated_only: false,
+            tag_template: None,
         }
     }
 
@@ -568,4 +1476,146 @@ mod test {
 
         assert_eq!(expected, test);
     }
+
+    #[rstest]
+    #[case::perf_feature("perf", Hierarchy::Feature, "perf")]
+    #[case::security_fix("Security", Hierarchy::Fix, "security")]
+    #[case::docs_none("docs", Hierarchy::None, "docs")]
+    fn test_set_type_level(
+        #[case] commit_type: &str,
+        #[case] level: Hierarchy,
+        #[case] expected_type: &str,
+    ) {
+        let test = CalculatorConfig::new()
+            .set_prefix("v")
+            .set_type_level(commit_type, level.clone());
+
+        let expected = CalculatorConfig {
+            prefix: String::from("v"),
+            report_bump: true,
+            type_levels: hash_map! {expected_type.to_string() => level},
+            ..Default::default()
+        };
+
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn test_add_no_release_types() {
+        let test = CalculatorConfig::new()
+            .set_prefix("v")
+            .set_type_level("docs", Hierarchy::Fix)
+            .add_no_release_types(vec!["docs".to_string(), "CI".to_string()]);
+
+        let expected = CalculatorConfig {
+            prefix: String::from("v"),
+            report_bump: true,
+            type_levels: hash_map! {
+                "docs".to_string() => Hierarchy::None,
+                "ci".to_string() => Hierarchy::None,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn test_apply_config_file() {
+        let config_file = ConfigFile::parse(
+            r#"
+prefix = "app-v"
+subdir = "app"
+required-files = ["CHANGELOG.md"]
+enforce-level = "fix"
+check = "feature"
+no-release = ["docs"]
+
+[type-levels]
+perf = "fix"
+"#,
+        )
+        .unwrap();
+
+        let test = CalculatorConfig::new()
+            .set_prefix("v")
+            .apply_config_file(&config_file);
+
+        let expected = CalculatorConfig {
+            prefix: String::from("app-v"),
+            subdir: Some(String::from("app")),
+            report_bump: true,
+            files: hash_set![OsString::from("CHANGELOG.md")],
+            enforce: Hierarchy::Fix,
+            threshold: Hierarchy::Feature,
+            type_levels: hash_map! {
+                "perf".to_string() => Hierarchy::Fix,
+                "docs".to_string() => Hierarchy::None,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn test_apply_empty_config_file_changes_nothing() {
+        let test = CalculatorConfig::new()
+            .set_prefix("v")
+            .apply_config_file(&ConfigFile::default());
+
+        assert_eq!(default_config(), test);
+    }
+
+    #[test]
+    fn test_config_file_round_trip() {
+        let config = CalculatorConfig::new()
+            .set_prefix("v")
+            .set_reporting_threshold(Hierarchy::Fix)
+            .set_type_level("perf", Hierarchy::Feature)
+            .add_required_files(vec![OsString::from("README.md")]);
+
+        let test = CalculatorConfig::new().apply_config_file(&config.config_file());
+
+        assert_eq!(config, test);
+    }
+
+    #[test]
+    fn test_config_file_reports_no_release_types() {
+        let config = CalculatorConfig::new()
+            .set_prefix("v")
+            .set_type_level("perf", Hierarchy::Feature)
+            .add_no_release_types(vec!["docs".to_string(), "ci".to_string()]);
+
+        let config_file = config.config_file();
+
+        assert_eq!(
+            Some(vec!["ci".to_string(), "docs".to_string()]),
+            config_file.no_release
+        );
+        assert_eq!(1, config_file.type_levels.len());
+        assert_eq!(
+            config,
+            CalculatorConfig::new().apply_config_file(&config_file)
+        );
+    }
+
+    #[test]
+    fn test_package_paths_round_trip() {
+        let config = CalculatorConfig::new()
+            .set_prefix("v")
+            .add_package_include("core", "proto/**")
+            .add_package_exclude("core", "CHANGELOG.md")
+            .set_ignore_root_files(true)
+            .add_include_paths(vec!["src/**".to_string()])
+            .add_exclude_paths(vec!["docs/**".to_string(), "*.md".to_string()])
+            .add_package_scope("core", "parser")
+            .add_include_scopes(vec!["parser".to_string()])
+            .add_exclude_scopes(vec!["docs".to_string()])
+            .set_tag_selection(TagSelection::Nearest);
+
+        let test = CalculatorConfig::new().apply_config_file(&config.config_file());
+
+        assert_eq!(config, test);
+    }
 }
@@ -1,18 +1,21 @@
 //! Represents a vector of conventional commits
 //!
 
-mod cmt_summary;
-use cmt_summary::CmtSummary;
+mod parsed_commit;
+pub use parsed_commit::{Footer, ParsedCommit};
 
 use std::{
     collections::{HashMap, HashSet},
     ffi::OsString,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
-use git2::{Repository, TreeWalkMode, TreeWalkResult};
+use git2::{Oid, Repository, TreeWalkMode, TreeWalkResult};
 
 use super::commit::Commit;
+use super::commit_record::{CommitRecord, SkipReason};
+use super::path_filter::{PathFilter, PathGlobs};
+use super::scope_filter::ScopeFilter;
 
 use crate::{Error, Workspace};
 
@@ -29,6 +32,10 @@ pub(crate) struct ConventionalCommits {
     /// Titles of commits detected as major-version dependency bumps.
     /// Used to emit advisory warnings to stderr after version calculation.
     pub(crate) major_dep_bumps: Vec<String>,
+    /// User supplied mapping of commit types to levels.
+    pub(crate) type_levels: HashMap<String, Hierarchy>,
+    /// Every commit found since the tag, including those skipped.
+    pub(crate) records: Vec<CommitRecord>,
 }
 
 impl ConventionalCommits {
@@ -36,44 +43,47 @@ impl ConventionalCommits {
         ConventionalCommits::default()
     }
 
+    /// Walk back from the `to` revision, or HEAD, to the `from` revision
+    /// collecting the commits that pass the filters.
+    ///
+    /// The revisions may be any revspec understood by git, such as a tag, a
+    /// branch or a commit id. Without a `from` revision the walk continues to
+    /// the root commit.
     pub(crate) fn walk_back_commits_to_tag_reference(
         repo: &Repository,
-        reference: &str,
-        subdir: Option<&str>,
-        package: Option<&str>,
+        from: Option<&str>,
+        to: Option<&str>,
+        filter: Option<&PathFilter>,
+        paths: &PathGlobs,
+        scopes: &ScopeFilter,
+        type_levels: &HashMap<String, Hierarchy>,
     ) -> Result<Self, Error> {
-        let subdir = get_subdir_for_package(package, subdir);
-
         log::debug!("repo opened to find conventional commits");
-        log::debug!("Searching for the tag: `{reference}`");
-        let tag_commit = match repo.find_reference(reference) {
-            Ok(reference) => match reference.peel_to_commit() {
-                Ok(commit) => commit,
-                Err(e) => {
-                    log::error!("Error finding the tag commit: {e:?}");
-                    return Err(Error::Git2(e));
-                }
-            },
-            Err(e) => {
-                log::error!("Error finding the tag reference: {e:?}");
-                return Err(Error::Git2(e));
-            }
+        let tag_commit = match from {
+            Some(from) => Some(find_tag_commit(repo, from)?),
+            None => None,
         };
-        let tag_tree = match tag_commit.tree() {
-            Ok(tree) => tree,
-            Err(e) => {
-                log::error!("Error finding the tag tree: {e:?}");
-                return Err(Error::Git2(e));
-            }
-        };
-        log::debug!("tag tree found: {tag_tree:?}");
 
         let mut revwalk = repo.revwalk()?;
         revwalk.set_sorting(git2::Sort::NONE)?;
-        revwalk.push_head()?;
-        log::debug!("starting the walk from the HEAD");
-        log::debug!("the reference to walk back to is: `{reference}`");
-        revwalk.hide_ref(reference)?;
+        match to {
+            Some(to) => {
+                let to_commit = repo.revparse_single(to)?.peel_to_commit()?;
+                log::debug!("starting the walk from `{to}`");
+                revwalk.push(to_commit.id())?;
+            }
+            None => {
+                log::debug!("starting the walk from the HEAD");
+                revwalk.push_head()?;
+            }
+        }
+        match tag_commit {
+            Some(tag_commit) => {
+                log::debug!("the commit to walk back to is: `{}`", tag_commit.id());
+                revwalk.hide(tag_commit.id())?;
+            }
+            None => log::debug!("walking back to the root commit"),
+        }
 
         macro_rules! filter_try {
             ($e:expr) => {
@@ -93,9 +103,8 @@ impl ConventionalCommits {
         });
 
         let mut conventional_commits = ConventionalCommits::new();
-        let mut file_names = HashSet::new();
+        conventional_commits.type_levels = type_levels.clone();
 
-        let mut tree_flag = true;
         // Walk back through the commits to collect the commit summary and identify conventional commits
         for commit in revwalk.flatten() {
             let cmt = Commit::new(commit.clone(), repo);
@@ -104,69 +113,271 @@ impl ConventionalCommits {
 
             if cmt.is_merge() {
                 log::debug!("Skipping merge commit: `{summary}`");
+                conventional_commits.skip(&commit, SkipReason::Merge);
+                continue;
+            }
+
+            let parsed = cmt.parse()?;
+            let commit_scopes = parsed.scopes();
+            if !scopes.selects(&commit_scopes) {
+                log::debug!("Skipping commit because the scope is not selected");
+                conventional_commits.skip(&commit, scope_skip_reason(&commit_scopes));
                 continue;
             }
 
             let files = cmt.files();
             log::debug!("files found: `{files:#?}`");
 
-            if let Some(subdir) = &subdir {
-                log::debug!("subdir: `{subdir}`");
-                let dup_files = files.clone();
-                let root_files: Vec<_> = dup_files
-                    .iter()
-                    .filter(|file| {
-                        let s = file.to_str().unwrap();
-                        !s.contains("/") && (s.ends_with(".toml") || s.ends_with(".lock"))
-                    })
-                    .collect();
-                log::debug!("root files: `{root_files:#?}`");
-                let mut qualified_files: Vec<_> = files
-                    .iter()
-                    .filter(|file| file.to_str().unwrap().contains(subdir))
-                    .collect();
-                log::debug!("qualified files: `{qualified_files:#?}`");
-                log::info!("Checking for root directory files changed in addition to {subdir}");
-                qualified_files.extend_from_slice(&root_files);
-
-                if qualified_files.is_empty() {
-                    log::debug!("Exiting loop because `{subdir}` not found");
+            let considered = paths.select(&files);
+            if !files.is_empty() && considered.is_empty() {
+                log::debug!("Skipping commit because only excluded paths changed");
+                conventional_commits.skip(&commit, SkipReason::Excluded);
+                continue;
+            }
+
+            if let Some(filter) = filter {
+                if !filter.qualifies(&considered) && !filter.has_scope(&commit_scopes) {
+                    log::debug!("Skipping commit because `{}` not changed", filter.prefix());
+                    conventional_commits.skip(&commit, SkipReason::OutsideSubdir(filter.prefix()));
                     continue;
                 }
             }
 
-            conventional_commits.push(&commit);
+            conventional_commits.include(&commit, &parsed, &files)?;
+        }
+        log::debug!("conventional commits found: {conventional_commits:#?}");
+
+        Ok(conventional_commits)
+    }
+
+    /// Walk back once from HEAD collecting the commits for each package of a workspace.
+    ///
+    /// Each package is given as the filter for its files and the commit of its
+    /// current version tag. A commit is counted for a package if it is not reachable
+    /// from the package tag and it changes files of the package. For a package
+    /// without a version tag all the commits back to the root commit are counted.
+    ///
+    /// The walk is in topological order so that the tags a commit is reachable
+    /// from are known before it is visited and are passed on to its parents.
+    pub(crate) fn walk_back_workspace(
+        repo: &Repository,
+        packages: &[(PathFilter, Option<Oid>)],
+        paths: &PathGlobs,
+        scopes: &ScopeFilter,
+        type_levels: &HashMap<String, Hierarchy>,
+    ) -> Result<Vec<Self>, Error> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+        revwalk.push_head()?;
+
+        // Commits reachable from every package tag are not needed by any package.
+        let tags: Vec<Oid> = packages.iter().filter_map(|(_, tag)| *tag).collect();
+        match tags.as_slice() {
+            _ if tags.len() < packages.len() => {
+                log::debug!("walking back to the root commit for the untagged packages");
+            }
+            [] => {}
+            [tag] => revwalk.hide(*tag)?,
+            tags => {
+                if let Ok(base) = repo.merge_base_many(tags) {
+                    log::debug!("hiding the commits reachable from `{base}`");
+                    revwalk.hide(base)?;
+                }
+            }
+        }
+
+        // The package tags each pending commit is reachable from, keyed by commit.
+        let mut reachable_from: HashMap<Oid, HashSet<Oid>> = HashMap::new();
+
+        let mut all_commits: Vec<ConventionalCommits> = packages
+            .iter()
+            .map(|_| {
+                let mut conventional_commits = ConventionalCommits::new();
+                conventional_commits.type_levels = type_levels.clone();
+                conventional_commits
+            })
+            .collect();
+
+        for id in revwalk {
+            let commit = repo.find_commit(id?)?;
+            let mut behind_tags = reachable_from.remove(&commit.id()).unwrap_or_default();
+            if tags.contains(&commit.id()) {
+                behind_tags.insert(commit.id());
+            }
+            for parent in commit.parent_ids() {
+                reachable_from
+                    .entry(parent)
+                    .or_default()
+                    .extend(&behind_tags);
+            }
+
+            let cmt = Commit::new(commit.clone(), repo);
+            log::debug!("commit found: `{}`", cmt.message()?);
+            let merge = cmt.is_merge();
+            let parsed = cmt.parse()?;
+            let commit_scopes = parsed.scopes();
+            let selected = scopes.selects(&commit_scopes);
+            let files = if merge || !selected {
+                vec![]
+            } else {
+                cmt.files()
+            };
+            let considered = paths.select(&files);
+            let excluded = !files.is_empty() && considered.is_empty();
 
-            for path in files {
-                if let Some(os_string) = path.file_name() {
-                    file_names.insert(OsString::from(os_string));
+            for ((filter, tag), conventional_commits) in packages.iter().zip(all_commits.iter_mut())
+            {
+                if tag.is_some_and(|tag| behind_tags.contains(&tag)) {
+                    continue;
+                }
+                if merge {
+                    conventional_commits.skip(&commit, SkipReason::Merge);
+                } else if !selected {
+                    conventional_commits.skip(&commit, scope_skip_reason(&commit_scopes));
+                } else if excluded {
+                    conventional_commits.skip(&commit, SkipReason::Excluded);
+                } else if !filter.qualifies(&considered) && !filter.has_scope(&commit_scopes) {
+                    conventional_commits.skip(&commit, SkipReason::OutsideSubdir(filter.prefix()));
+                } else {
+                    conventional_commits.include(&commit, &parsed, &files)?;
                 }
             }
+        }
+
+        Ok(all_commits)
+    }
 
-            if tree_flag {
-                let tree = commit.tree().unwrap();
-                let mut all_files = HashSet::new();
-                tree.walk(TreeWalkMode::PreOrder, |_, entry| {
-                    let _ = entry.name().map(|os_string| {
-                        log::trace!("file found: {:?}", os_string);
-                        all_files.insert(OsString::from(os_string));
-                    });
-                    TreeWalkResult::Ok
-                })?;
-                conventional_commits.all_files = all_files;
-                tree_flag = false;
+    /// Walk back from HEAD to the shared version tag of a workspace, or to the root
+    /// commit if there is no tag, collecting the commits that change files in any
+    /// of the member directories.
+    ///
+    /// Returns the commits and, for each member, whether the member was changed.
+    pub(crate) fn walk_back_lockstep(
+        repo: &Repository,
+        reference: Option<&str>,
+        members: &[PathFilter],
+        paths: &PathGlobs,
+        scopes: &ScopeFilter,
+        type_levels: &HashMap<String, Hierarchy>,
+    ) -> Result<(Self, Vec<bool>), Error> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::NONE)?;
+        revwalk.push_head()?;
+        match reference {
+            Some(reference) => {
+                log::debug!("the reference to walk back to is: `{reference}`");
+                revwalk.hide_ref(reference)?;
             }
+            None => log::debug!("walking back to the root commit"),
         }
-        conventional_commits.changed_files = file_names;
-        log::debug!("conventional commits found: {conventional_commits:#?}");
 
-        Ok(conventional_commits)
+        let mut conventional_commits = ConventionalCommits::new();
+        conventional_commits.type_levels = type_levels.clone();
+        let mut changed = vec![false; members.len()];
+
+        for id in revwalk {
+            let commit = repo.find_commit(id?)?;
+            let cmt = Commit::new(commit.clone(), repo);
+            log::debug!("commit found: `{}`", cmt.message()?);
+
+            if cmt.is_merge() {
+                conventional_commits.skip(&commit, SkipReason::Merge);
+                continue;
+            }
+
+            let parsed = cmt.parse()?;
+            let commit_scopes = parsed.scopes();
+            if !scopes.selects(&commit_scopes) {
+                conventional_commits.skip(&commit, scope_skip_reason(&commit_scopes));
+                continue;
+            }
+
+            let files = cmt.files();
+            let considered = paths.select(&files);
+            if !files.is_empty() && considered.is_empty() {
+                conventional_commits.skip(&commit, SkipReason::Excluded);
+                continue;
+            }
+
+            let mut in_member = false;
+            for (member, changed) in members.iter().zip(changed.iter_mut()) {
+                if member.qualifies(&considered) || member.has_scope(&commit_scopes) {
+                    *changed = true;
+                    in_member = true;
+                }
+            }
+            if in_member {
+                conventional_commits.include(&commit, &parsed, &files)?;
+            } else {
+                conventional_commits.skip(
+                    &commit,
+                    SkipReason::OutsideSubdir("workspace members".to_string()),
+                );
+            }
+        }
+
+        Ok((conventional_commits, changed))
     }
 
-    pub(crate) fn push(&mut self, commit: &git2::Commit) -> &Self {
-        if commit.summary().is_ok() && commit.summary().unwrap().is_some() {
-            let summary = commit.summary().unwrap().unwrap();
-            self.update_from_summary(summary);
+    /// Raise the change level to the level inherited from released workspace dependencies.
+    pub(crate) fn inherit(&mut self, level: TopType, dependencies: &[String]) {
+        let summary = format!("release of {}", dependencies.join(", "));
+        log::debug!("Inheriting level `{level}` from the {summary}");
+        self.records.push(CommitRecord {
+            id: "-".to_string(),
+            summary: summary.clone(),
+            commit_type: Some("deps".to_string()),
+            level: level.clone(),
+            skipped: None,
+        });
+        self.commits.push(summary);
+        if level == TopType::Breaking {
+            self.breaking = true;
+        }
+        if level > self.top_type {
+            self.top_type = level;
+        }
+    }
+
+    /// Count the commit and collect the names of the files it changed.
+    ///
+    /// The files in the tree of the first (latest) commit counted are
+    /// collected as the files in the repository.
+    fn include(
+        &mut self,
+        commit: &git2::Commit,
+        parsed: &ParsedCommit,
+        files: &[PathBuf],
+    ) -> Result<(), Error> {
+        self.push(commit, parsed);
+
+        for path in files {
+            if let Some(os_string) = path.file_name() {
+                self.changed_files.insert(OsString::from(os_string));
+            }
+        }
+
+        if self.all_files.is_empty() {
+            let tree = commit.tree()?;
+            let mut all_files = HashSet::new();
+            tree.walk(TreeWalkMode::PreOrder, |_, entry| {
+                let _ = entry.name().map(|os_string| {
+                    log::trace!("file found: {:?}", os_string);
+                    all_files.insert(OsString::from(os_string));
+                });
+                TreeWalkResult::Ok
+            })?;
+            self.all_files = all_files;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn push(&mut self, commit: &git2::Commit, parsed: &ParsedCommit) -> &Self {
+        let summary = commit.summary().ok().flatten().unwrap_or_default();
+        self.update_from_parsed(summary, parsed);
+        if let Some(record) = self.records.last_mut() {
+            record.id = short_id(commit);
         }
 
         if let Ok(opt_os) = commit.summary() {
@@ -176,33 +387,72 @@ impl ConventionalCommits {
         self
     }
 
-    fn update_from_summary(&mut self, summary: &str) -> &Self {
-        let cmt_summary = CmtSummary::parse(summary).unwrap();
-        let commit_type = cmt_summary.type_string();
+    /// Record a commit that is not counted in the calculation.
+    fn skip(&mut self, commit: &git2::Commit, reason: SkipReason) {
+        self.records.push(CommitRecord {
+            id: short_id(commit),
+            summary: commit
+                .summary()
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+                .to_string(),
+            skipped: Some(reason),
+            ..Default::default()
+        });
+    }
+
+    #[cfg(test)]
+    fn update_from_message(&mut self, message: &str) -> &Self {
+        match ParsedCommit::parse(message) {
+            Ok(parsed) => {
+                self.update_from_parsed(message.lines().next().unwrap_or_default(), &parsed)
+            }
+            Err(e) => {
+                log::warn!("Skipping the commit `{message}` as it cannot be parsed: {e}");
+                self
+            }
+        }
+    }
+
+    /// Count the parsed commit and raise the change level if required.
+    fn update_from_parsed(&mut self, summary: &str, parsed: &ParsedCommit) -> &Self {
+        let commit_type = parsed.type_string();
+        let level = TopType::for_type(&commit_type, &self.type_levels);
 
         log::trace!(
             "Commit: ({}) {} {}",
             commit_type,
-            cmt_summary.title,
-            Hierarchy::parse(&cmt_summary.type_.clone().unwrap_or("".to_string()))
-                .unwrap_or(Hierarchy::Other),
+            parsed.description(),
+            level,
         );
         let counter = self.counts.entry(commit_type.clone()).or_insert(0);
         *counter += 1;
 
-        if cmt_summary.is_major_dep_bump() {
-            log::debug!("Major dependency bump detected: {}", cmt_summary.title);
-            self.major_dep_bumps.push(cmt_summary.title.clone());
+        self.records.push(CommitRecord {
+            summary: summary.to_string(),
+            commit_type: parsed.commit_type().map(String::from),
+            level: if parsed.is_breaking() {
+                TopType::Breaking
+            } else {
+                level.clone()
+            },
+            ..Default::default()
+        });
+
+        if parsed.is_major_dep_bump() {
+            log::debug!("Major dependency bump detected: {}", parsed.description());
+            self.major_dep_bumps.push(parsed.description().to_string());
         }
 
         if !self.breaking {
             log::trace!("Not broken yet!");
-            if cmt_summary.breaking {
+            if parsed.is_breaking() {
                 log::trace!("Breaking change found!");
-                self.breaking = cmt_summary.breaking;
+                self.breaking = true;
                 self.top_type = TopType::Breaking;
-            } else if TopType::parse(&commit_type).unwrap() > self.top_type {
-                self.top_type = TopType::parse(&commit_type).unwrap();
+            } else if level > self.top_type {
+                self.top_type = level;
                 log::trace!("New top type found {}!", self.top_type);
             };
         };
@@ -210,32 +460,66 @@ impl ConventionalCommits {
     }
 }
 
-fn get_subdir_for_package(package: Option<&str>, subdir: Option<&str>) -> Option<String> {
-    if package.is_none() {
-        let subdir = subdir?;
-        let s = subdir.to_string();
-        return Some(s);
-    };
+/// Find the commit the walk back stops at.
+fn find_tag_commit<'a>(repo: &'a Repository, from: &str) -> Result<git2::Commit<'a>, Error> {
+    log::debug!("Searching for the revision: `{from}`");
+    match repo.revparse_single(from) {
+        Ok(object) => match object.peel_to_commit() {
+            Ok(commit) => Ok(commit),
+            Err(e) => {
+                log::error!("Error finding the tag commit: {e:?}");
+                Err(Error::Git2(e))
+            }
+        },
+        Err(e) => {
+            log::error!("Error finding the tag reference: {e:?}");
+            Err(Error::Git2(e))
+        }
+    }
+}
 
-    let rel_package = package.unwrap();
-    log::info!("Running release for package: {rel_package}");
+/// The ids of the commits reachable from HEAD but not from the `tag` commit.
+fn scope_skip_reason(scopes: &[String]) -> SkipReason {
+    SkipReason::Scope((!scopes.is_empty()).then(|| scopes.join(",")))
+}
 
-    let path = Path::new("./Cargo.toml");
-    let workspace = Workspace::new(path).unwrap();
+fn short_id(commit: &git2::Commit) -> String {
+    commit.id().to_string().chars().take(7).collect()
+}
 
-    let packages = workspace.packages();
+/// Find the directory, relative to the repository working directory, of the
+/// package in the workspace containing `dir`.
+pub(crate) fn get_subdir_for_package(
+    dir: &Path,
+    workdir: &Path,
+    package: Option<&str>,
+    subdir: Option<&str>,
+) -> Result<Option<String>, Error> {
+    let Some(rel_package) = package else {
+        return Ok(subdir.map(String::from));
+    };
+    log::info!("Running release for package: {rel_package}");
 
-    if let Some(packages) = packages {
-        for package in packages {
-            log::debug!("Found workspace package: {}", package.name);
-            if package.name != rel_package {
-                continue;
-            }
-            return Some(package.member);
+    let workspace = match Workspace::discover(dir) {
+        Ok(workspace) => workspace,
+        Err(Error::NotWorkspace) => {
+            log::warn!("No workspace found to select the package `{rel_package}`");
+            return Ok(None);
         }
+        Err(e) => return Err(e),
     };
-    None
+
+    for package in workspace.packages()? {
+        log::debug!("Found workspace package: {}", package.name);
+        if package.name == rel_package {
+            return Ok(Some(workspace.member_path(&package.member, workdir)));
+        }
+    }
+
+    log::warn!("Package `{rel_package}` not found in the workspace");
+    Ok(None)
 }
+
 #[cfg(test)]
 mod tests {
     use std::cmp::Ordering;
@@ -286,11 +570,11 @@ mod tests {
 
         let mut con_commits = super::ConventionalCommits::new();
         // A regular fix commit — should not be flagged
-        con_commits.update_from_summary("fix: fix an existing feature");
+        con_commits.update_from_message("fix: fix an existing feature");
         // A major dep bump — should be collected
-        con_commits.update_from_summary("fix(deps): update serde to v2.0.0");
+        con_commits.update_from_message("fix(deps): update serde to v2.0.0");
         // Another major dep bump via (major) marker
-        con_commits.update_from_summary("chore(deps): bump tokio (major)");
+        con_commits.update_from_message("chore(deps): bump tokio (major)");
 
         // top_type should still be Fix (dep bumps don't elevate version)
         assert_eq!(TopType::Fix, con_commits.top_type);
@@ -303,13 +587,60 @@ mod tests {
         get_test_logger();
 
         let mut con_commits = super::ConventionalCommits::new();
-        con_commits.update_from_summary("fix: fix an existing feature");
-        con_commits.update_from_summary("feat: add new feature");
-        con_commits.update_from_summary("chore(deps): update serde to v1.0.200");
+        con_commits.update_from_message("fix: fix an existing feature");
+        con_commits.update_from_message("feat: add new feature");
+        con_commits.update_from_message("chore(deps): update serde to v1.0.200");
 
         assert!(con_commits.major_dep_bumps.is_empty());
     }
 
+    #[rstest]
+    #[case::summary_only("feat: new api", false, TopType::Feature)]
+    #[case::breaking_footer("feat: new api\n\nBREAKING CHANGE: removed X", true, TopType::Breaking)]
+    #[case::breaking_hyphen_footer(
+        "fix: new api\n\nBody text.\n\nBREAKING-CHANGE: removed X",
+        true,
+        TopType::Breaking
+    )]
+    fn test_breaking_change_footer_sets_breaking(
+        #[case] message: &str,
+        #[case] expected_breaking: bool,
+        #[case] expected_top_type: TopType,
+    ) {
+        get_test_logger();
+
+        let mut con_commits = super::ConventionalCommits::new();
+        con_commits.update_from_message(message);
+
+        assert_eq!(expected_breaking, con_commits.breaking);
+        assert_eq!(expected_top_type, con_commits.top_type);
+    }
+
+    #[rstest]
+    #[case::perf_feature(&["perf: faster"], "perf", Hierarchy::Feature, TopType::Feature)]
+    #[case::case_insensitive(&["PERF: faster"], "perf", Hierarchy::Fix, TopType::Fix)]
+    #[case::docs_none(&["docs: update"], "docs", Hierarchy::None, TopType::None)]
+    #[case::docs_none_with_chore(&["docs: update", "chore: tidy"], "docs", Hierarchy::None, TopType::Other)]
+    #[case::feat_demoted(&["feat: add"], "feat", Hierarchy::Other, TopType::Other)]
+    fn test_type_levels_override_top_type(
+        #[case] messages: &[&str],
+        #[case] commit_type: &str,
+        #[case] level: Hierarchy,
+        #[case] expected_top_type: TopType,
+    ) {
+        get_test_logger();
+
+        let mut con_commits = super::ConventionalCommits::new();
+        con_commits
+            .type_levels
+            .insert(commit_type.to_string(), level);
+        for message in messages {
+            con_commits.update_from_message(message);
+        }
+
+        assert_eq!(expected_top_type, con_commits.top_type);
+    }
+
     #[rstest]
     #[case::feat_other_feat("feat: add new feature", TopType::Other, TopType::Feature)]
     #[case::emoji_feat_other_feat("✨ feat: add new feature", TopType::Other, TopType::Feature)]
@@ -575,7 +906,7 @@ mod tests {
             con_commits.breaking = true;
         }
         con_commits.top_type = base_top_type;
-        con_commits.update_from_summary(title);
+        con_commits.update_from_message(title);
 
         println!("Conventional commits: {con_commits:#?}");
 
@@ -583,4 +914,24 @@ mod tests {
 
         assert_eq!(expected_top_type, con_commits.top_type);
     }
+
+    #[rstest]
+    #[case::raises_level(TopType::Other, TopType::Fix, TopType::Fix, false)]
+    #[case::keeps_higher_level(TopType::Feature, TopType::Fix, TopType::Feature, false)]
+    #[case::breaking(TopType::Fix, TopType::Breaking, TopType::Breaking, true)]
+    fn test_inherit(
+        #[case] base_top_type: TopType,
+        #[case] level: TopType,
+        #[case] expected_top_type: TopType,
+        #[case] expected_breaking: bool,
+    ) {
+        let mut con_commits = super::ConventionalCommits::new();
+        con_commits.top_type = base_top_type;
+
+        con_commits.inherit(level, &["one".to_string()]);
+
+        assert_eq!(expected_top_type, con_commits.top_type);
+        assert_eq!(expected_breaking, con_commits.breaking);
+        assert_eq!(vec!["release of one".to_string()], con_commits.commits);
+    }
 }
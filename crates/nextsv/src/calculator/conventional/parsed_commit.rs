@@ -0,0 +1,630 @@
+//! Parser for commit messages following the
+//! [Conventional Commits 1.0.0](https://www.conventionalcommits.org/en/v1.0.0/)
+//! specification.
+//!
+//! In addition to the specification an optional emoji (or gitmoji shortcode)
+//! prefix is accepted before the type, e.g. `✨ feat: add feature`.
+//!
+
+use std::{fmt, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::Error;
+
+const BREAKING_CHANGE: &str = "BREAKING CHANGE";
+const BREAKING_CHANGE_HYPHEN: &str = "BREAKING-CHANGE";
+
+static HEADER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?P<emoji>(?::[\w+-]+:|[^\sA-Za-z0-9]+)\s+)?(?P<type>[A-Za-z][\w-]*)(?:\((?P<scope>[^()]*)\))?(?P<breaking>!)?: (?P<description>\S.*)$",
+    )
+    .expect("valid header regex")
+});
+
+static FOOTER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<token>BREAKING CHANGE|[A-Za-z][\w-]*)(?P<separator>: | #)(?P<value>.*)$")
+        .expect("valid footer regex")
+});
+
+static MAJOR_VERSION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bv(\d+)\.").expect("valid version regex"));
+
+/// A footer (trailer) found at the end of a conventional commit message.
+///
+/// Footers take the form `<token>: <value>` or `<token> #<value>`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Footer {
+    pub(crate) token: String,
+    pub(crate) separator: String,
+    pub(crate) value: String,
+}
+
+impl Footer {
+    /// The footer token, e.g. `Refs` or `BREAKING CHANGE`.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// The separator between the token and the value, either `": "` or `" #"`.
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// The footer value. Multi-line values are joined with newlines.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns true if the footer is a `BREAKING CHANGE` or `BREAKING-CHANGE` footer.
+    ///
+    /// The specification requires the breaking change token to be uppercase so
+    /// this is the one token that is compared case sensitively.
+    pub fn is_breaking(&self) -> bool {
+        self.token == BREAKING_CHANGE || self.token == BREAKING_CHANGE_HYPHEN
+    }
+
+    /// Returns true if the token matches `token` ignoring ASCII case.
+    pub fn is_token(&self, token: &str) -> bool {
+        self.token.eq_ignore_ascii_case(token)
+    }
+}
+
+impl fmt::Display for Footer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.token, self.separator, self.value)
+    }
+}
+
+/// A commit message parsed into its conventional commit components.
+///
+/// Messages that do not have a conventional header are still parsed: the
+/// type is `None`, the whole header becomes the description and the body and
+/// footers are collected in the same way as for a conventional commit.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), nextsv::Error> {
+/// use nextsv::ParsedCommit;
+///
+/// let message = "feat(api)!: remove the v1 endpoints
+///
+/// The v1 endpoints have been deprecated since 0.9.
+///
+/// Refs: #123
+/// BREAKING CHANGE: clients must use the v2 endpoints";
+///
+/// let commit = ParsedCommit::parse(message)?;
+///
+/// assert_eq!(Some("feat"), commit.commit_type());
+/// assert_eq!(Some("api"), commit.scope());
+/// assert_eq!("remove the v1 endpoints", commit.description());
+/// assert_eq!(Some("The v1 endpoints have been deprecated since 0.9."), commit.body());
+/// assert_eq!(2, commit.footers().len());
+/// assert!(commit.is_breaking());
+/// assert_eq!(vec!["clients must use the v2 endpoints"], commit.breaking_notes());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ParsedCommit {
+    pub(crate) emoji: Option<String>,
+    pub(crate) type_: Option<String>,
+    pub(crate) scope: Option<String>,
+    pub(crate) breaking: bool,
+    pub(crate) description: String,
+    pub(crate) body: Option<String>,
+    pub(crate) footers: Vec<Footer>,
+    pub(crate) breaking_notes: Vec<String>,
+}
+
+impl ParsedCommit {
+    /// Parse a full commit message.
+    ///
+    /// Types and footer tokens are matched case insensitively; types are
+    /// reported in lowercase. The `BREAKING CHANGE` footer token must be
+    /// uppercase as required by the specification.
+    pub fn parse(message: &str) -> Result<Self, Error> {
+        log::debug!("Message to parse: `{message}`");
+        let lines: Vec<&str> = message.lines().map(str::trim_end).collect();
+        let header = lines.first().copied().unwrap_or_default();
+
+        let mut parsed = Self::parse_header(header);
+
+        // Paragraphs after the header, each a list of lines
+        let mut paragraphs: Vec<Vec<&str>> = vec![];
+        let mut current = vec![];
+        for line in lines.iter().skip(1) {
+            if line.trim().is_empty() {
+                if !current.is_empty() {
+                    paragraphs.push(current);
+                    current = vec![];
+                }
+            } else {
+                current.push(*line);
+            }
+        }
+        if !current.is_empty() {
+            paragraphs.push(current);
+        }
+
+        // The footer section is the trailing run of paragraphs that each start
+        // with a footer token.
+        let re_footer = &*FOOTER_REGEX;
+        let mut footer_start = paragraphs.len();
+        while footer_start > 0 && re_footer.is_match(paragraphs[footer_start - 1][0]) {
+            footer_start -= 1;
+        }
+
+        let body = paragraphs[..footer_start]
+            .iter()
+            .map(|p| p.join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if !body.is_empty() {
+            parsed.body = Some(body);
+        }
+
+        for paragraph in &paragraphs[footer_start..] {
+            for line in paragraph {
+                if let Some(captures) = re_footer.captures(line) {
+                    parsed.footers.push(Footer {
+                        token: captures["token"].to_string(),
+                        separator: captures["separator"].to_string(),
+                        value: captures["value"].trim().to_string(),
+                    });
+                } else if let Some(footer) = parsed.footers.last_mut() {
+                    // Continuation of the previous footer value
+                    footer.value.push('\n');
+                    footer.value.push_str(line.trim());
+                }
+            }
+        }
+
+        for footer in parsed.footers.iter().filter(|f| f.is_breaking()) {
+            parsed.breaking = true;
+            parsed.breaking_notes.push(footer.value.clone());
+        }
+        if parsed.breaking && parsed.breaking_notes.is_empty() {
+            parsed.breaking_notes.push(parsed.description.clone());
+        }
+
+        log::debug!("Parsed commit: {parsed:?}");
+
+        Ok(parsed)
+    }
+
+    fn parse_header(header: &str) -> Self {
+        if let Some(captures) = HEADER_REGEX.captures(header) {
+            log::trace!("Captures: {captures:#?}");
+            Self {
+                emoji: captures.name("emoji").map(|m| m.as_str().to_string()),
+                type_: captures.name("type").map(|m| m.as_str().to_lowercase()),
+                scope: captures
+                    .name("scope")
+                    .map(|m| m.as_str().trim().to_string())
+                    .filter(|s| !s.is_empty()),
+                breaking: captures.name("breaking").is_some(),
+                description: captures["description"].trim().to_string(),
+                ..Default::default()
+            }
+        } else {
+            Self {
+                description: header.to_string(),
+                ..Default::default()
+            }
+        }
+    }
+
+    /// Returns true if the header follows the conventional commit format.
+    pub fn is_conventional(&self) -> bool {
+        self.type_.is_some()
+    }
+
+    /// The emoji prefix including the trailing whitespace, if present.
+    pub fn emoji(&self) -> Option<&str> {
+        self.emoji.as_deref()
+    }
+
+    /// The commit type in lowercase, e.g. `feat`.
+    pub fn commit_type(&self) -> Option<&str> {
+        self.type_.as_deref()
+    }
+
+    /// The commit type or an empty string for non-conventional commits.
+    pub fn type_string(&self) -> String {
+        self.type_.clone().unwrap_or_default()
+    }
+
+    /// The scope, e.g. `parser` in `feat(parser): ...`.
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    /// The scopes in lowercase, split on commas so that
+    /// `feat(parser, lexer): ...` has the scopes `parser` and `lexer`.
+    pub(crate) fn scopes(&self) -> Vec<String> {
+        self.scope
+            .as_deref()
+            .map(|scope| {
+                scope
+                    .split(',')
+                    .map(|scope| scope.trim().to_lowercase())
+                    .filter(|scope| !scope.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The description following the type and scope.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The body between the header and the footers.
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    /// The footers in the order they appear.
+    pub fn footers(&self) -> &[Footer] {
+        &self.footers
+    }
+
+    /// Returns true if the commit is marked breaking by `!` or a breaking change footer.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking
+    }
+
+    /// The breaking change notes.
+    ///
+    /// These are the values of the `BREAKING CHANGE` footers or, where the
+    /// commit is marked breaking with `!` only, the description.
+    pub fn breaking_notes(&self) -> Vec<&str> {
+        self.breaking_notes.iter().map(String::as_str).collect()
+    }
+
+    /// Returns true if this commit appears to be a major-version dependency bump.
+    ///
+    /// Detection is heuristic and conservative: false negatives are acceptable,
+    /// false positives are not. A commit is considered a major dep bump when:
+    /// - The type is `fix` or `chore`
+    /// - The scope contains `deps`
+    /// - The description contains either `(major)` or a version pattern `v<N>` where N >= 2
+    pub fn is_major_dep_bump(&self) -> bool {
+        let type_matches = matches!(self.type_.as_deref(), Some("fix") | Some("chore"));
+
+        let scope_has_deps = self
+            .scope
+            .as_deref()
+            .map(|s| s.contains("deps"))
+            .unwrap_or(false);
+
+        if !type_matches || !scope_has_deps {
+            return false;
+        }
+
+        // Check for explicit (major) marker
+        if self.description.contains("(major)") {
+            return true;
+        }
+
+        // Check for `v<N>` where N >= 2 anywhere in the description.
+        // Pattern: "v" then one or more digits, then "." — the leading digit must be >= 2.
+        for cap in MAJOR_VERSION_REGEX.captures_iter(&self.description) {
+            if let Some(m) = cap.get(1) {
+                if let Ok(major) = m.as_str().parse::<u64>() {
+                    if major >= 2 {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl fmt::Display for ParsedCommit {
+    /// Display the header of the commit.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.type_.is_none() {
+            return write!(f, "{}", self.description);
+        }
+        write!(
+            f,
+            "{}{}{}{}: {}",
+            self.emoji.clone().unwrap_or_default(),
+            self.type_.clone().unwrap_or_default(),
+            self.scope
+                .as_ref()
+                .map_or("".to_string(), |s| format!("({s})")),
+            if self.breaking { "!" } else { "" },
+            self.description
+        )
+    }
+}
+
+//test module
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use color_eyre::Result;
+    use log::LevelFilter;
+    use rstest::rstest;
+
+    fn get_test_logger() {
+        let mut builder = env_logger::Builder::new();
+        builder.filter(None, LevelFilter::Debug);
+        builder.format_timestamp_secs().format_module_path(false);
+        let _ = builder.try_init();
+    }
+
+    #[test]
+    fn test_parsed_commit_parse() {
+        let parsed = ParsedCommit::parse("feat: add new feature").unwrap();
+
+        assert_eq!(parsed.description, "add new feature");
+        assert_eq!(parsed.type_, Some("feat".to_string()));
+        assert_eq!(parsed.scope, None);
+        assert!(!parsed.breaking);
+
+        let parsed = ParsedCommit::parse("feat(core): add new feature").unwrap();
+        assert_eq!(parsed.description, "add new feature");
+        assert_eq!(parsed.type_, Some("feat".to_string()));
+        assert_eq!(parsed.scope, Some("core".to_string()));
+        assert!(!parsed.breaking);
+
+        let parsed = ParsedCommit::parse("feat(core)!: add new feature").unwrap();
+        assert_eq!(parsed.description, "add new feature");
+        assert_eq!(parsed.type_, Some("feat".to_string()));
+        assert_eq!(parsed.scope, Some("core".to_string()));
+        assert!(parsed.breaking);
+    }
+
+    #[test]
+    fn test_parsed_commit_parse_with_breaking_scope() {
+        let parsed = ParsedCommit::parse("feat(core)!: add new feature").unwrap();
+        assert_eq!(parsed.description, "add new feature");
+        assert_eq!(parsed.type_, Some("feat".to_string()));
+        assert_eq!(parsed.scope, Some("core".to_string()));
+        assert!(parsed.breaking);
+        assert_eq!(vec!["add new feature"], parsed.breaking_notes());
+    }
+
+    #[test]
+    fn test_parsed_commit_parse_with_security_scope() {
+        let parsed = ParsedCommit::parse("fix(security): fix security vulnerability").unwrap();
+        assert_eq!(parsed.description, "fix security vulnerability");
+        assert_eq!(parsed.type_, Some("fix".to_string()));
+        assert_eq!(parsed.scope, Some("security".to_string()));
+        assert!(!parsed.breaking);
+    }
+
+    #[test]
+    fn test_parsed_commit_parse_with_deprecate_scope() {
+        let parsed = ParsedCommit::parse("chore(deprecate): deprecate old feature").unwrap();
+        assert_eq!(parsed.description, "deprecate old feature");
+        assert_eq!(parsed.type_, Some("chore".to_string()));
+        assert_eq!(parsed.scope, Some("deprecate".to_string()));
+        assert!(!parsed.breaking);
+    }
+
+    #[test]
+    fn test_parsed_commit_parse_without_scope() {
+        let parsed = ParsedCommit::parse("docs: update documentation").unwrap();
+        assert_eq!(parsed.description, "update documentation");
+        assert_eq!(parsed.type_, Some("docs".to_string()));
+        assert_eq!(parsed.scope, None);
+        assert!(!parsed.breaking);
+    }
+
+    #[test]
+    fn test_parsed_commit_parse_issue_172() {
+        let parsed = ParsedCommit::parse(
+            "chore(config.yml): update jerus-org/circleci-toolkit orb version to 0.4.0",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.description,
+            "update jerus-org/circleci-toolkit orb version to 0.4.0"
+        );
+        assert_eq!(parsed.type_, Some("chore".to_string()));
+        assert_eq!(parsed.scope, Some("config.yml".to_string()));
+        assert!(!parsed.breaking);
+    }
+
+    #[test]
+    fn test_parsed_commit_parse_message_with_breaking_footer() {
+        let parsed = ParsedCommit::parse("feat: new api\n\nBREAKING CHANGE: removed X").unwrap();
+        assert_eq!(parsed.description, "new api");
+        assert_eq!(parsed.type_, Some("feat".to_string()));
+        assert!(parsed.breaking);
+        assert_eq!(vec!["removed X"], parsed.breaking_notes());
+    }
+
+    #[rstest]
+    #[case::summary_only("feat: new api", false)]
+    #[case::summary_bang("feat!: new api", true)]
+    #[case::footer_space("feat: new api\n\nBREAKING CHANGE: removed X", true)]
+    #[case::footer_hyphen("fix: new api\n\nBREAKING-CHANGE: removed X", true)]
+    #[case::footer_after_body(
+        "fix: new api\n\nSome body text.\n\nRefs: #123\nBREAKING CHANGE: removed X",
+        true
+    )]
+    #[case::lowercase_token_ignored("feat: new api\n\nbreaking change: removed X", false)]
+    #[case::mention_in_body_ignored(
+        "feat: new api\n\nThis is not a BREAKING CHANGE: at all",
+        false
+    )]
+    #[case::breaking_paragraph_before_body(
+        "feat: new api\n\nBREAKING CHANGE: removed X\n\nMore body text.",
+        false
+    )]
+    fn test_parsed_commit_parse_breaking(#[case] message: &str, #[case] expected: bool) {
+        get_test_logger();
+        let parsed = ParsedCommit::parse(message).unwrap();
+        assert_eq!(expected, parsed.breaking, "message: {message}");
+    }
+
+    #[test]
+    fn test_parse_multi_paragraph_body_and_footers() {
+        get_test_logger();
+        let message = "fix(parser): handle empty input\n\
+            \n\
+            First paragraph of the body\n\
+            wraps onto a second line.\n\
+            \n\
+            Second paragraph.\n\
+            \n\
+            Reviewed-by: Z\n\
+            Fixes #42\n\
+            BREAKING CHANGE: the parser now returns an error\n  \
+            for empty input instead of panicking\n";
+
+        let parsed = ParsedCommit::parse(message).unwrap();
+
+        assert_eq!(Some("fix"), parsed.commit_type());
+        assert_eq!(Some("parser"), parsed.scope());
+        assert_eq!("handle empty input", parsed.description());
+        assert_eq!(
+            Some("First paragraph of the body\nwraps onto a second line.\n\nSecond paragraph."),
+            parsed.body()
+        );
+        assert_eq!(3, parsed.footers().len());
+        assert_eq!("Reviewed-by", parsed.footers()[0].token());
+        assert_eq!(": ", parsed.footers()[0].separator());
+        assert_eq!("Z", parsed.footers()[0].value());
+        assert_eq!("Fixes", parsed.footers()[1].token());
+        assert_eq!(" #", parsed.footers()[1].separator());
+        assert_eq!("42", parsed.footers()[1].value());
+        assert!(parsed.footers()[2].is_breaking());
+        assert_eq!(
+            vec!["the parser now returns an error\nfor empty input instead of panicking"],
+            parsed.breaking_notes()
+        );
+        assert!(parsed.is_breaking());
+    }
+
+    #[rstest]
+    #[case::lower("feat: add", "feat")]
+    #[case::upper("FEAT: add", "feat")]
+    #[case::mixed("Fix(core): add", "fix")]
+    #[case::hyphenated("Security-Fix: add", "security-fix")]
+    fn test_type_is_case_insensitive(#[case] message: &str, #[case] expected: &str) {
+        let parsed = ParsedCommit::parse(message).unwrap();
+        assert_eq!(Some(expected), parsed.commit_type());
+    }
+
+    #[rstest]
+    #[case::none("feat: add", vec![])]
+    #[case::single("feat(Parser): add", vec!["parser"])]
+    #[case::multiple("feat(parser, lexer): add", vec!["parser", "lexer"])]
+    #[case::empty_entries("feat(parser,,): add", vec!["parser"])]
+    fn test_scopes(#[case] message: &str, #[case] expected: Vec<&str>) {
+        let parsed = ParsedCommit::parse(message).unwrap();
+        assert_eq!(expected, parsed.scopes());
+    }
+
+    #[rstest]
+    #[case::refs_lower("feat: x\n\nrefs: #1", "Refs", true)]
+    #[case::refs_upper("feat: x\n\nREFS: #1", "refs", true)]
+    #[case::other_token("feat: x\n\nAcked-by: Y", "refs", false)]
+    fn test_footer_token_is_case_insensitive(
+        #[case] message: &str,
+        #[case] token: &str,
+        #[case] expected: bool,
+    ) {
+        let parsed = ParsedCommit::parse(message).unwrap();
+        assert_eq!(expected, parsed.footers()[0].is_token(token));
+    }
+
+    #[rstest]
+    #[case::merge("Merge branch 'main' of github.com:jerusdp/nextsv into fix/version-level")]
+    #[case::no_space("feat:missing space")]
+    #[case::empty_description("feat: ")]
+    #[case::sentence("Update README.md: fix typo")]
+    fn test_non_conventional_header(#[case] message: &str) {
+        let parsed = ParsedCommit::parse(message).unwrap();
+        assert!(!parsed.is_conventional());
+        assert_eq!(None, parsed.commit_type());
+        assert_eq!(message.trim_end(), parsed.description());
+        assert_eq!(message.trim_end(), parsed.to_string());
+    }
+
+    #[test]
+    fn test_non_conventional_message_keeps_footers() {
+        let parsed =
+            ParsedCommit::parse("Update everything\n\nBREAKING CHANGE: all of it").unwrap();
+        assert!(!parsed.is_conventional());
+        assert!(parsed.is_breaking());
+    }
+
+    #[rstest]
+    #[case::unicode("✨ feat: add new feature", "✨ ")]
+    #[case::variation_selector("♻️ refactor(remove): Remove unused code", "♻️ ")]
+    #[case::shortcode(":sparkles: feat: add new feature", ":sparkles: ")]
+    fn test_emoji_prefix(#[case] message: &str, #[case] expected: &str) {
+        let parsed = ParsedCommit::parse(message).unwrap();
+        assert_eq!(Some(expected), parsed.emoji());
+        assert_eq!(message, parsed.to_string());
+    }
+
+    #[rstest]
+    #[case("fix(deps): update serde to v2.0.0", true)]
+    #[case("fix(deps): update serde to v1.0.200", false)]
+    #[case("chore(deps): bump tokio (major)", true)]
+    #[case("feat: add new feature", false)]
+    #[case("fix(deps): update serde to v0.9.0 to v0.10.0", false)]
+    #[case("fix(deps): update serde to v10.0.0", true)]
+    #[case("chore(deps): bump serde from v1.0.0 to v2.0.0", true)]
+    #[case("fix(security): fix security vulnerability", false)]
+    #[case("chore(config): update settings", false)]
+    fn test_is_major_dep_bump(#[case] title: &str, #[case] expected: bool) -> Result<()> {
+        get_test_logger();
+        let parsed = ParsedCommit::parse(title).unwrap();
+        assert_eq!(expected, parsed.is_major_dep_bump(), "commit: {title}");
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("feat: add new feature", "feat")]
+    #[case("✨ feat: add new feature", "feat")]
+    #[case("feat: add new feature", "feat")]
+    #[case("feat: add new feature", "feat")]
+    #[case("feat: add new feature", "feat")]
+    #[case("✨ feat: add new feature", "feat")]
+    #[case("fix: fix an existing feature", "fix")]
+    #[case("🐛 fix: fix an existing feature", "fix")]
+    #[case("style: fix typo and lint issues", "style")]
+    #[case("💄 style: fix typo and lint issues", "style")]
+    #[case("test: update tests", "test")]
+    #[case("fix(security): Fix security vulnerability", "fix")]
+    #[case("chore(deps): Update dependencies", "chore")]
+    #[case("🔧 chore(deps): Update dependencies", "chore")]
+    #[case("refactor(remove): Remove unused code", "refactor")]
+    #[case("♻️ refactor(remove): Remove unused code", "refactor")]
+    #[case("docs(deprecate): Deprecate old API", "docs")]
+    #[case("📚 docs(deprecate): Deprecate old API", "docs")]
+    #[case("ci(other-scope): Update CI configuration", "ci")]
+    #[case("👷 ci(other-scope): Update CI configuration", "ci")]
+    #[case("test!: Update test cases", "test")]
+    #[case::issue_172(
+        "chore(config.yml): update jerus-org/circleci-toolkit orb version to 0.4.0",
+        "chore"
+    )]
+    #[case::with_emoji("✨ feat(ci): add optional flag for push failure handling", "feat")]
+    fn test_calculate_kind_and_description(
+        #[case] title: &str,
+        #[case] expected_type: &str,
+    ) -> Result<()> {
+        get_test_logger();
+
+        let parsed = ParsedCommit::parse(title).unwrap();
+        assert_eq!(expected_type, &parsed.type_.unwrap());
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use std::{cmp, fmt};
 
 use clap::{Parser, ValueEnum};
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 
 use crate::Error;
 
@@ -18,10 +19,18 @@ use super::TopType;
 /// | Feature   | feat                     | Minor       | Minor           |
 /// | Fix       | fix, revert              | Patch       | Patch           |
 /// | Other     | docs, style, refactor, perf, test, chore, build, ci, etc. | Patch       | Patch           |
+/// | None      | none by default; assigned with a type level mapping | None        | None            |
 ///
 /// The hierachy is listed in order of importance.
-#[derive(Debug, PartialEq, Eq, Clone, ValueEnum, Default, Parser)]
+#[derive(Debug, PartialEq, Eq, Clone, ValueEnum, Default, Parser, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Hierarchy {
+    /// None variant represents changes that do not require a release.
+    ///
+    /// Only assigned with a type level mapping, so not offered as a level to
+    /// check or require.
+    #[clap(skip)]
+    None,
     /// Other variant represents other changes.
     #[default]
     Other,
@@ -39,7 +48,10 @@ impl Ord for Hierarchy {
             (Hierarchy::Breaking, Hierarchy::Breaking)
             | (Hierarchy::Feature, Hierarchy::Feature)
             | (Hierarchy::Fix, Hierarchy::Fix)
-            | (Hierarchy::Other, Hierarchy::Other) => cmp::Ordering::Equal,
+            | (Hierarchy::Other, Hierarchy::Other)
+            | (Hierarchy::None, Hierarchy::None) => cmp::Ordering::Equal,
+            (Hierarchy::None, _) => cmp::Ordering::Less,
+            (_, Hierarchy::None) => cmp::Ordering::Greater,
             (Hierarchy::Other, _) => cmp::Ordering::Less,
             (Hierarchy::Breaking, _) => cmp::Ordering::Greater,
             (Hierarchy::Fix, Hierarchy::Other) => cmp::Ordering::Greater,
@@ -71,6 +83,17 @@ impl Hierarchy {
             _ => Hierarchy::Other,
         })
     }
+
+    /// The lowercase name of the level as used on the command line
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Hierarchy::Breaking => "breaking",
+            Hierarchy::Feature => "feature",
+            Hierarchy::Fix => "fix",
+            Hierarchy::Other => "other",
+            Hierarchy::None => "none",
+        }
+    }
 }
 
 impl fmt::Display for Hierarchy {
@@ -80,6 +103,7 @@ impl fmt::Display for Hierarchy {
             Hierarchy::Feature => write!(f, "{}", "[Minor]".yellow()),
             Hierarchy::Fix => write!(f, "{}", "[Patch]".green()),
             Hierarchy::Other => write!(f, "{}", "[Patch]".white()),
+            Hierarchy::None => write!(f, "{}", "[None]".white()),
         }
     }
 }
@@ -91,7 +115,7 @@ impl From<&TopType> for Hierarchy {
             TopType::Feature => Hierarchy::Feature,
             TopType::Fix => Hierarchy::Fix,
             TopType::Other => Hierarchy::Other,
-            TopType::None => Hierarchy::Other,
+            TopType::None => Hierarchy::None,
         }
     }
 }
@@ -105,6 +129,7 @@ impl AsRef<Hierarchy> for Hierarchy {
 #[cfg(test)]
 mod test {
     use super::Hierarchy;
+    use super::TopType;
 
     use colored::Colorize;
     use rstest::rstest;
@@ -147,11 +172,22 @@ mod test {
         assert_eq!(two.cmp(&one), std::cmp::Ordering::Greater);
     }
 
+    #[rstest]
+    #[case::other(Hierarchy::Other)]
+    #[case::fix(Hierarchy::Fix)]
+    #[case::feature(Hierarchy::Feature)]
+    #[case::breaking(Hierarchy::Breaking)]
+    fn test_none_is_lowest(#[case] level: Hierarchy) {
+        assert!(Hierarchy::None < level);
+        assert_eq!(level.cmp(&Hierarchy::None), std::cmp::Ordering::Greater);
+    }
+
     #[rstest]
     #[case::breaking(Hierarchy::Breaking, format!("{}","[Major]".red()))]
     #[case::non_production(Hierarchy::Feature, format!("{}","[Minor]".yellow()))]
     #[case::production(Hierarchy::Fix, format!("{}","[Patch]".green()))]
     #[case::release(Hierarchy::Other, format!("{}","[Patch]".white()))]
+    #[case::none(Hierarchy::None, format!("{}","[None]".white()))]
     fn display_value(#[case] test: Hierarchy, #[case] expected: String) {
         assert_eq!(expected, test.to_string());
     }
@@ -177,4 +213,14 @@ mod test {
 
         assert_eq!(expected, test_level);
     }
+
+    #[rstest]
+    #[case::breaking(TopType::Breaking, Hierarchy::Breaking)]
+    #[case::feature(TopType::Feature, Hierarchy::Feature)]
+    #[case::fix(TopType::Fix, Hierarchy::Fix)]
+    #[case::other(TopType::Other, Hierarchy::Other)]
+    #[case::none(TopType::None, Hierarchy::None)]
+    fn from_top_type(#[case] top_type: TopType, #[case] expected: Hierarchy) {
+        assert_eq!(expected, Hierarchy::from(&top_type));
+    }
 }
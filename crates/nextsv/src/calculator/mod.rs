@@ -1,26 +1,45 @@
+mod apply;
 mod bump;
 mod change_bump;
 mod commit;
+mod commit_record;
 mod config;
 mod conventional;
 mod force_bump;
 mod hierarchy;
 mod next_version;
+mod path_filter;
+mod report;
 mod route;
+mod scope_filter;
+mod tag_list;
+mod tag_selection;
 mod top_type;
+mod version_history;
+mod workspace_calculator;
 
+pub use self::apply::{Applied, ApplyConfig, FileChange};
 use self::bump::Bump;
 pub use self::config::CalculatorConfig;
 
+pub use self::conventional::{Footer, ParsedCommit};
 pub use self::force_bump::ForceBump;
+pub use self::report::{PackageReport, ReleaseReport, Report, TagReport};
 pub(crate) use self::route::Route;
+pub use self::tag_list::TagList;
+pub use self::tag_selection::TagSelection;
 pub(crate) use self::top_type::TopType;
-pub(crate) use self::{conventional::ConventionalCommits, next_version::NextVersion};
+pub use self::version_history::VersionHistory;
+pub use self::workspace_calculator::WorkspaceCalculator;
+pub(crate) use self::{
+    conventional::{get_subdir_for_package, ConventionalCommits},
+    next_version::NextVersion,
+};
 use crate::version::VersionTag;
 use crate::Error;
 pub(crate) use change_bump::ChangeBump;
-use git2::Repository;
 pub use hierarchy::Hierarchy;
+use std::ffi::OsString;
 
 /// Used to calculate the bump and next version number.
 ///
@@ -33,27 +52,123 @@ pub struct Calculator {
     change_bump: Option<ChangeBump>,
     bump: Bump,
     next_version: NextVersion,
+    route: Route,
+    missing_files: Vec<OsString>,
+    decisions: Vec<String>,
+    /// True if no version tag was found so the next version is the first version
+    first_release: bool,
 }
 
 impl Calculator {
     /// Collect the data and apply the configuration to calculate the
     /// required outputs.
     ///
+    /// If no version tag is found and the initial version is set, or the
+    /// repository has no tags at all, all the commits from the root commit are
+    /// collected and the first version is reported as the next version.
+    ///
     /// This method is typically executed by [`CalculatorConfig::build`].
-    pub(crate) fn execute(mut config: CalculatorConfig) -> Result<Self, Error> {
-        let repo = Repository::open(".")?;
+    pub(crate) fn execute(config: CalculatorConfig) -> Result<Self, Error> {
+        let (repo, workdir) = config.open_repository()?;
 
-        let package = config.package.as_deref().unwrap_or_default();
+        let subdir = get_subdir_for_package(
+            config.repository_dir(),
+            &workdir,
+            config.package.as_deref(),
+            config.subdir.as_deref(),
+        )?;
+        let template = config.tag_template(
+            config.package.as_deref(),
+            subdir.as_deref().unwrap_or_default(),
+        )?;
 
-        let current_version = VersionTag::find_in_repo(&repo, package, config.prefix.as_str())?;
+        let from_version = config
+            .from
+            .as_deref()
+            .and_then(|from| VersionTag::from_revision(&repo, from, &template));
+        let current_version = match from_version {
+            Some(version) => Some(version),
+            None => match VersionTag::find_in_repo(
+                &repo,
+                &template,
+                config.tag_selection,
+                config.to.as_deref(),
+                config.annotated_only,
+            ) {
+                Ok(version) => Some(version),
+                Err(Error::NoVersionTag) => {
+                    log::info!("No version tag found so the first version is calculated");
+                    None
+                }
+                Err(e) => return Err(e),
+            },
+        };
+        let from = config
+            .from
+            .clone()
+            .or_else(|| current_version.as_ref().map(|version| version.to_string()));
+        let to = config.to.clone();
+
+        let filter = match &subdir {
+            Some(subdir) => Some(config.path_filter(config.package.as_deref(), subdir)?),
+            None => None,
+        };
 
         let conventional = ConventionalCommits::walk_back_commits_to_tag_reference(
             &repo,
-            current_version.to_string().as_str(),
-            config.subdir.as_deref(),
-            config.package.as_deref(),
+            from.as_deref(),
+            to.as_deref(),
+            filter.as_ref(),
+            &config.path_globs()?,
+            &config.scope_filter(),
+            &config.type_levels,
         )?;
 
+        let range_set = config.from.is_some() || to.is_some();
+        let mut calculator = match current_version {
+            Some(current_version) => Calculator::calculate(config, current_version, conventional)?,
+            None => {
+                let manifest_dir = match &subdir {
+                    Some(subdir) => workdir.join(subdir),
+                    None => workdir.clone(),
+                };
+                let first_version = config.first_version(&repo, &manifest_dir, &template)?;
+                Calculator::first_release(config, first_version, conventional)
+            }
+        };
+        if range_set {
+            calculator.decisions.insert(
+                0,
+                format!(
+                    "Commits counted from `{}` to `{}`",
+                    from.as_deref()
+                        .unwrap_or("the root commit")
+                        .trim_start_matches("refs/tags/"),
+                    to.as_deref().unwrap_or("HEAD")
+                ),
+            );
+        }
+
+        Ok(calculator)
+    }
+
+    /// Apply the configuration to the commits found since the current version
+    /// to calculate the bump and next version.
+    pub(crate) fn calculate(
+        mut config: CalculatorConfig,
+        current_version: VersionTag,
+        conventional: ConventionalCommits,
+    ) -> Result<Self, Error> {
+        let mut decisions = vec![format!(
+            "Highest change level from the commits is `{}`{}",
+            conventional.top_type.name(),
+            if conventional.breaking {
+                " (breaking change found)"
+            } else {
+                ""
+            }
+        )];
+
         let test_level: Hierarchy = conventional.top_type.as_ref().into();
         log::debug!(
             "File enforcement required at `{:?}` and change level `{:?}`",
@@ -79,16 +194,34 @@ impl Calculator {
 
                 if !missing_files.is_empty() {
                     log::error!("Missing required files: {missing_files:?}");
-                    return Ok(Calculator::exit_none(
-                        config.clone(),
-                        current_version,
-                        conventional,
-                        None,
+                    decisions.push(format!(
+                        "Required files not updated: {}; bump is `none`",
+                        missing_files
+                            .iter()
+                            .map(|file| file.to_string_lossy())
+                            .collect::<Vec<_>>()
+                            .join(", ")
                     ));
+                    let mut calculator =
+                        Calculator::exit_none(config.clone(), current_version, conventional, None);
+                    calculator.missing_files = missing_files;
+                    calculator.decisions = decisions;
+                    return Ok(calculator);
                 }
             } else {
                 log::debug!("All required files are present");
             };
+            if !config.files.is_empty() {
+                decisions.push(format!(
+                    "Required files checked at level `{}`: all updated",
+                    config.enforce.name()
+                ));
+            }
+        } else if !config.files.is_empty() {
+            decisions.push(format!(
+                "Required files not checked as the change level is below `{}`",
+                config.enforce.name()
+            ));
         }
 
         let change_bump = Some(ChangeBump::calculate(
@@ -98,6 +231,9 @@ impl Calculator {
         let route = Route::calculate(&current_version.semantic_version);
         let mut bump = Bump::calculate(&route, &conventional);
         log::debug!("Calculated the bump `{bump}` based on the route `{route}`");
+        decisions.push(format!(
+            "Bump calculated from the commits on the {route} route is `{bump}`"
+        ));
 
         // Check the force level and apply if required
         log::debug!("Force level: {:?}", config.force);
@@ -105,22 +241,32 @@ impl Calculator {
             log::trace!("Forcing bump level: `{force_level:?}`");
             log::trace!("Current version: `{}`", current_version.semantic_version);
             bump = force_level.to_bump(&current_version.semantic_version);
+            decisions.push(format!("Bump forced with `{force_level:?}` to `{bump}`"));
         };
 
         // Check the threshold and exit early if it has not been met.
-        if config.threshold > conventional.top_type.as_ref().into() {
+        // With no qualifying changes there is nothing to hold back.
+        if conventional.top_type != TopType::None
+            && config.threshold > conventional.top_type.as_ref().into()
+        {
             log::info!(
                 "The highest level change `{}` does not exceed the threshold `{}`",
                 std::convert::Into::<Hierarchy>::into(conventional.top_type.as_ref()),
                 config.threshold
             );
 
-            return Ok(Calculator::exit_none(
-                config,
-                current_version,
-                conventional,
-                change_bump,
+            decisions.push(format!(
+                "Threshold `{}` not met by change level `{}`; bump is `none`",
+                config.threshold.name(),
+                conventional.top_type.name()
             ));
+            let mut calculator =
+                Calculator::exit_none(config, current_version, conventional, change_bump);
+            calculator.decisions = decisions;
+            return Ok(calculator);
+        }
+        if config.threshold > Hierarchy::Other {
+            decisions.push(format!("Threshold `{}` met", config.threshold.name()));
         }
 
         log::debug!(
@@ -134,6 +280,7 @@ impl Calculator {
 
         if bump == Bump::None {
             config.report_number = false;
+            config.report_tag = false;
         }
 
         log::debug!(
@@ -150,14 +297,18 @@ impl Calculator {
                     version.semantic_version.patch = 0;
 
                     bump = Bump::Custom(next_version.version_number().to_string());
+                    decisions.push("First production version 1.0.0 forced".to_string());
                 }
             }
         }
 
-        // Emit advisory warnings to stderr for major dependency bumps.
-        // These go to stderr only — stdout is machine-consumed by CI pipelines.
-        for title in &conventional.major_dep_bumps {
-            eprintln!("WARNING: major dependency bump detected: {title}");
+        if bump == Bump::None {
+            decisions.push("No new version is required".to_string());
+        } else {
+            decisions.push(format!(
+                "Next version is `{}` with bump `{bump}`",
+                next_version.version_number()
+            ));
         }
 
         let calculated_result = Ok(Calculator {
@@ -167,6 +318,10 @@ impl Calculator {
             change_bump,
             bump,
             next_version,
+            route,
+            missing_files: vec![],
+            decisions,
+            first_release: false,
         });
 
         log::trace!("Calculated result for reporting: {calculated_result:?}");
@@ -179,6 +334,15 @@ impl Calculator {
         self.bump.clone().to_string()
     }
 
+    /// Advisory warnings raised by the calculation, e.g. for major dependency bumps.
+    pub fn warnings(&self) -> Vec<String> {
+        self.conventional
+            .major_dep_bumps
+            .iter()
+            .map(|title| format!("major dependency bump detected: {title}"))
+            .collect()
+    }
+
     /// Output the next version number
     pub fn next_version_number(&self) -> String {
         if let NextVersion::Updated(version) = &self.next_version {
@@ -287,13 +451,229 @@ impl Calculator {
     /// beta
     /// v1.0.0-beta.5
     /// ```
+    ///
+    /// ## Package reporting the next tag
+    ///
+    /// With a current version tag of `nextsv-v0.19.35` and `fix` conventional
+    /// commits and the following configuration requiring the bump, next
+    /// version number and next tag to be reported:
+    ///
+    /// ```no_run
+    /// # use nextsv::CalculatorConfig;
+    /// # fn main() -> Result<(), nextsv::Error> {
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_package(Some("nextsv"))
+    ///         .set_version_report(true)
+    ///         .set_tag_report(true)
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// The output is the following:
+    ///
+    /// ```console
+    /// patch
+    /// 0.19.36
+    /// nextsv-v0.19.36
+    /// ```
     pub fn report(&self) -> String {
         log::debug!("Config for reporting: {:?}", self.config);
-        match (self.config.report_bump, self.config.report_number) {
-            (true, true) => format!("{}\n{}", self.bump, self.next_version.version_number()),
-            (false, true) => self.next_version.version_number(),
-            (true, false) => self.bump.to_string(),
-            (false, false) => String::from(""),
+        let mut lines = vec![];
+        if self.config.report_bump {
+            lines.push(self.bump.to_string());
+        }
+        if self.config.report_number {
+            lines.push(self.next_version.version_number());
+        }
+        if self.config.report_tag {
+            lines.push(self.next_version.tag_name());
+        }
+
+        lines.join("\n")
+    }
+
+    /// Output the calculation as a structured [`Report`].
+    ///
+    /// All values are reported irrespective of the bump and version
+    /// reporting flags set in the configuration.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nextsv::CalculatorConfig;
+    /// # fn main() -> Result<(), nextsv::Error> {
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .build()?;
+    ///
+    ///     let report = calculator.structured_report();
+    ///     println!("{} -> {:?}", report.current_version, report.next_version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn structured_report(&self) -> Report {
+        let (next_version, next_tag) = match &self.next_version {
+            NextVersion::Updated(_) if self.bump != Bump::None => (
+                Some(self.next_version.version_number()),
+                Some(self.next_version.tag_name()),
+            ),
+            _ => (None, None),
+        };
+        let top_type = Hierarchy::from(&self.conventional.top_type);
+
+        let (current_version, current_tag) = if self.first_release {
+            (String::new(), String::new())
+        } else {
+            (
+                self.current_version.semantic_version.to_string(),
+                self.current_version.tag_name(),
+            )
+        };
+        let tag = (!self.first_release).then_some(&self.current_version);
+        let annotation = tag.and_then(|tag| tag.annotation.as_ref());
+
+        Report {
+            current_version,
+            current_tag,
+            current_tag_annotated: annotation.is_some(),
+            current_tag_tagger: annotation.and_then(|annotation| annotation.tagger.clone()),
+            current_tag_date: tag.and_then(|tag| tag.date_string()),
+            current_tag_message: annotation.map(|annotation| annotation.message.clone()),
+            next_version,
+            next_tag,
+            bump: self.bump.to_string(),
+            change_bump: self.change_bump.unwrap_or_default().to_string(),
+            route: self.route.to_string(),
+            top_type,
+            commit_counts: self.conventional.counts.clone().into_iter().collect(),
+            breaking: self.conventional.breaking,
+            major_dep_bumps: self.conventional.major_dep_bumps.clone(),
+            missing_files: self
+                .missing_files
+                .iter()
+                .map(|file| file.to_string_lossy().to_string())
+                .collect(),
+        }
+    }
+
+    /// Output an explanation of how the bump and next version were calculated.
+    ///
+    /// The explanation lists the version tag found, the route taken, every commit
+    /// found since the tag with the level it contributed or the reason it was
+    /// skipped, and the chain of decisions leading to the reported bump.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nextsv::CalculatorConfig;
+    /// # fn main() -> Result<(), nextsv::Error> {
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .build()?;
+    ///
+    ///     println!("{}", calculator.explain());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ```console
+    /// Current tag: v1.1.0
+    /// Tagged: 2024-05-01 10:12:45 +0100 by Jane Doe <jane@example.com>
+    /// Tag message: Release 1.1.0
+    /// Current version: 1.1.0
+    /// Route: production
+    ///
+    /// Commits:
+    ///   3f2a1b4 feature  feat: add the explain command
+    ///   9c8d7e6 fix      fix: correct spelling
+    ///   5a4b3c2 skipped  Merge branch 'main' (merge commit)
+    ///
+    /// Decisions:
+    ///   1. Highest change level from the commits is `feature`
+    ///   2. Bump calculated from the commits on the production route is `minor`
+    ///   3. Next version is `1.2.0` with bump `minor`
+    /// ```
+    pub fn explain(&self) -> String {
+        let (current_tag, current_version) = if self.first_release {
+            ("none".to_string(), "none".to_string())
+        } else {
+            (
+                self.current_version.tag_name(),
+                self.current_version.semantic_version.to_string(),
+            )
+        };
+        let mut lines = vec![format!("Current tag: {current_tag}")];
+        if let (false, Some(date)) = (self.first_release, self.current_version.date_string()) {
+            match &self.current_version.annotation {
+                Some(annotation) => {
+                    lines.push(match &annotation.tagger {
+                        Some(tagger) => format!("Tagged: {date} by {tagger}"),
+                        None => format!("Tagged: {date}"),
+                    });
+                    if let Some(summary) = annotation.message.lines().next() {
+                        lines.push(format!("Tag message: {summary}"));
+                    }
+                }
+                None => lines.push(format!("Tagged: {date} (lightweight tag)")),
+            }
+        }
+        lines.extend([
+            format!("Current version: {current_version}"),
+            format!("Route: {}", self.route),
+            String::new(),
+            String::from("Commits:"),
+        ]);
+        if self.conventional.records.is_empty() {
+            lines.push(String::from("  none found since the tag"));
+        }
+        for record in &self.conventional.records {
+            lines.push(format!("  {record}"));
+        }
+        lines.push(String::new());
+        lines.push(String::from("Decisions:"));
+        for (i, decision) in self.decisions.iter().enumerate() {
+            lines.push(format!("  {}. {decision}", i + 1));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Report the first version as the next version when no version tag is
+    /// found.
+    ///
+    /// The commits and any forced bump do not change the first version; the
+    /// commits are reported for information.
+    pub(crate) fn first_release(
+        config: CalculatorConfig,
+        first_version: VersionTag,
+        conventional: ConventionalCommits,
+    ) -> Calculator {
+        let version = first_version.semantic_version.to_string();
+        let decisions = vec![
+            format!(
+                "Highest change level from the commits is `{}`",
+                conventional.top_type.name()
+            ),
+            "No version tag found; commits counted from the root commit".to_string(),
+            format!(
+                "First version is `{version}` with tag `{}`",
+                first_version.tag_name()
+            ),
+        ];
+
+        Calculator {
+            config,
+            route: Route::calculate(&first_version.semantic_version),
+            next_version: NextVersion::Updated(first_version.clone()),
+            current_version: first_version,
+            conventional,
+            change_bump: None,
+            bump: Bump::Custom(version),
+            missing_files: vec![],
+            decisions,
+            first_release: true,
         }
     }
 
@@ -305,8 +685,10 @@ impl Calculator {
     ) -> Calculator {
         let bump = Bump::None;
         let next_version = NextVersion::None;
+        let route = Route::calculate(&current_version.semantic_version);
         config.report_bump = true;
         config.report_number = false;
+        config.report_tag = false;
 
         Calculator {
             config,
@@ -315,6 +697,10 @@ impl Calculator {
             change_bump,
             bump,
             next_version,
+            route,
+            missing_files: vec![],
+            decisions: vec![],
+            first_release: false,
         }
     }
 }
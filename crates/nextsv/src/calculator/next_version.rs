@@ -2,6 +2,8 @@ use crate::version::{PreRelease, PreReleaseType, VersionTag};
 
 use super::{bump::Bump, ChangeBump};
 
+// Only one next version is held for each calculation so the size is not boxed away.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
 pub(crate) enum NextVersion {
     #[default]
@@ -17,6 +19,15 @@ impl NextVersion {
         }
     }
 
+    /// The name of the tag for the next version, including the prefix and
+    /// any suffix of the current version tag.
+    pub(crate) fn tag_name(&self) -> String {
+        match self {
+            NextVersion::Updated(version) => version.tag_name(),
+            NextVersion::None => String::new(),
+        }
+    }
+
     pub(crate) fn calculate(
         current_version: &VersionTag,
         mut bump: Bump,
@@ -203,7 +214,7 @@ mod test {
     use crate::calculator::bump::Bump;
     use crate::calculator::ChangeBump;
     use crate::test_utils::*;
-    use crate::version::{PreRelease, VersionTag};
+    use crate::version::{PreRelease, TagTemplate, VersionTag};
     use log::LevelFilter;
 
     use rstest::rstest;
@@ -215,6 +226,10 @@ mod test {
         let _ = builder.try_init();
     }
 
+    fn version_tag(version: &str) -> VersionTag {
+        VersionTag::first(version, &TagTemplate::from_prefix("", "v")).unwrap()
+    }
+
     #[rstest]
     #[case::none(0, 0, 0, "", "0.0.0")]
     #[case::non_production(0, 7, 10, "", "0.7.10")]
@@ -240,6 +255,27 @@ mod test {
         assert_eq!(expected, test.version_number());
     }
 
+    #[rstest]
+    #[case::prefix("v{version}", "0.7.9", "v0.7.9")]
+    #[case::package_prefix("nextsv-v{version}", "0.19.36", "nextsv-v0.19.36")]
+    #[case::pre_release("nextsv-v{version}", "1.0.0-rc.1", "nextsv-v1.0.0-rc.1")]
+    #[case::suffix("{version}-release", "1.2.3", "1.2.3-release")]
+    fn test_updated_tag_name(
+        #[case] template: &str,
+        #[case] version: &str,
+        #[case] expected: &str,
+    ) {
+        let template = TagTemplate::new(template, "", "").unwrap();
+        let test = NextVersion::Updated(VersionTag::first(version, &template).unwrap());
+
+        assert_eq!(expected, test.tag_name());
+    }
+
+    #[test]
+    fn test_none_tag_name() {
+        assert_eq!("", NextVersion::None.tag_name());
+    }
+
     #[rstest]
     fn test_calculation_of_next_version(
         #[values(
@@ -257,123 +293,99 @@ mod test {
     ) {
         get_test_logger();
 
-        let current_version = VersionTag::parse(tag, "v").unwrap();
+        let current_version = version_tag(tag.trim_start_matches("refs/tags/v"));
         let mut change_bump = None;
 
         let expected = match tag {
             "refs/tags/v0.7.9" => match bump {
                 Bump::None | Bump::Release | Bump::Custom(_) => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9", "v").unwrap())
-                }
-                Bump::Rc => NextVersion::Updated(VersionTag::parse("v0.7.9-rc.1", "v").unwrap()),
-                Bump::Beta => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-beta.1", "v").unwrap())
-                }
-                Bump::Alpha => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-alpha.1", "v").unwrap())
+                    NextVersion::Updated(version_tag("0.7.9"))
                 }
+                Bump::Rc => NextVersion::Updated(version_tag("0.7.9-rc.1")),
+                Bump::Beta => NextVersion::Updated(version_tag("0.7.9-beta.1")),
+                Bump::Alpha => NextVersion::Updated(version_tag("0.7.9-alpha.1")),
                 Bump::Patch => {
                     change_bump = Some(ChangeBump::Patch);
-                    NextVersion::Updated(VersionTag::parse("v0.7.10", "v").unwrap())
+                    NextVersion::Updated(version_tag("0.7.10"))
                 }
                 Bump::Minor => {
                     change_bump = Some(ChangeBump::Minor);
-                    NextVersion::Updated(VersionTag::parse("v0.8.0", "v").unwrap())
+                    NextVersion::Updated(version_tag("0.8.0"))
                 }
                 Bump::Major | Bump::First => {
                     change_bump = Some(ChangeBump::Major);
-                    NextVersion::Updated(VersionTag::parse("v1.0.0", "v").unwrap())
+                    NextVersion::Updated(version_tag("1.0.0"))
                 }
             },
             "refs/tags/v1.7.9" => match bump {
                 Bump::None | Bump::Release | Bump::First | Bump::Custom(_) => {
-                    NextVersion::Updated(VersionTag::parse("v1.7.9", "v").unwrap())
-                }
-                Bump::Rc => NextVersion::Updated(VersionTag::parse("v1.7.9-rc.1", "v").unwrap()),
-                Bump::Beta => {
-                    NextVersion::Updated(VersionTag::parse("v1.7.9-beta.1", "v").unwrap())
-                }
-                Bump::Alpha => {
-                    NextVersion::Updated(VersionTag::parse("v1.7.9-alpha.1", "v").unwrap())
+                    NextVersion::Updated(version_tag("1.7.9"))
                 }
+                Bump::Rc => NextVersion::Updated(version_tag("1.7.9-rc.1")),
+                Bump::Beta => NextVersion::Updated(version_tag("1.7.9-beta.1")),
+                Bump::Alpha => NextVersion::Updated(version_tag("1.7.9-alpha.1")),
                 Bump::Patch => {
                     change_bump = Some(ChangeBump::Patch);
-                    NextVersion::Updated(VersionTag::parse("v1.7.10", "v").unwrap())
+                    NextVersion::Updated(version_tag("1.7.10"))
                 }
                 Bump::Minor => {
                     change_bump = Some(ChangeBump::Patch);
-                    NextVersion::Updated(VersionTag::parse("v1.8.0", "v").unwrap())
+                    NextVersion::Updated(version_tag("1.8.0"))
                 }
                 Bump::Major => {
                     change_bump = Some(ChangeBump::Patch);
-                    NextVersion::Updated(VersionTag::parse("v2.0.0", "v").unwrap())
+                    NextVersion::Updated(version_tag("2.0.0"))
                 }
             },
             "refs/tags/v0.7.9-alpha.1" => match bump {
-                Bump::None => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-alpha.1", "v").unwrap())
-                }
+                Bump::None => NextVersion::Updated(version_tag("0.7.9-alpha.1")),
                 Bump::Alpha | Bump::Patch | Bump::Minor | Bump::Major => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-alpha.2", "v").unwrap())
+                    NextVersion::Updated(version_tag("0.7.9-alpha.2"))
                 }
-                Bump::Beta => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-beta.1", "v").unwrap())
+                Bump::Beta => NextVersion::Updated(version_tag("0.7.9-beta.1")),
+                Bump::Rc => NextVersion::Updated(version_tag("0.7.9-rc.1")),
+                Bump::Custom(ref pre) => {
+                    NextVersion::Updated(version_tag(&format!("0.7.9-{pre}.1")))
                 }
-                Bump::Rc => NextVersion::Updated(VersionTag::parse("v0.7.9-rc.1", "v").unwrap()),
-                Bump::Custom(ref pre) => NextVersion::Updated(
-                    VersionTag::parse(format!("v0.7.9-{pre}.1").as_str(), "v").unwrap(),
-                ),
-                Bump::Release => NextVersion::Updated(VersionTag::parse("v0.7.9", "v").unwrap()),
-                Bump::First => NextVersion::Updated(VersionTag::parse("v1.0.0", "v").unwrap()),
+                Bump::Release => NextVersion::Updated(version_tag("0.7.9")),
+                Bump::First => NextVersion::Updated(version_tag("1.0.0")),
             },
             "refs/tags/v0.7.9-beta.1" => match bump {
-                Bump::None => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-beta.1", "v").unwrap())
-                }
-                Bump::Alpha => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-alpha.1", "v").unwrap())
-                }
+                Bump::None => NextVersion::Updated(version_tag("0.7.9-beta.1")),
+                Bump::Alpha => NextVersion::Updated(version_tag("0.7.9-alpha.1")),
                 Bump::Beta | Bump::Patch | Bump::Minor | Bump::Major => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-beta.2", "v").unwrap())
+                    NextVersion::Updated(version_tag("0.7.9-beta.2"))
+                }
+                Bump::Rc => NextVersion::Updated(version_tag("0.7.9-rc.1")),
+                Bump::Custom(ref pre) => {
+                    NextVersion::Updated(version_tag(&format!("0.7.9-{pre}.1")))
                 }
-                Bump::Rc => NextVersion::Updated(VersionTag::parse("v0.7.9-rc.1", "v").unwrap()),
-                Bump::Custom(ref pre) => NextVersion::Updated(
-                    VersionTag::parse(format!("v0.7.9-{pre}.1").as_str(), "v").unwrap(),
-                ),
-                Bump::Release => NextVersion::Updated(VersionTag::parse("v0.7.9", "v").unwrap()),
-                Bump::First => NextVersion::Updated(VersionTag::parse("v1.0.0", "v").unwrap()),
+                Bump::Release => NextVersion::Updated(version_tag("0.7.9")),
+                Bump::First => NextVersion::Updated(version_tag("1.0.0")),
             },
             "refs/tags/v0.7.9-rc.1" => match bump {
-                Bump::None => NextVersion::Updated(VersionTag::parse("v0.7.9-rc.1", "v").unwrap()),
-                Bump::Alpha => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-alpha.1", "v").unwrap())
+                Bump::None => NextVersion::Updated(version_tag("0.7.9-rc.1")),
+                Bump::Alpha => NextVersion::Updated(version_tag("0.7.9-alpha.1")),
+                Bump::Beta => NextVersion::Updated(version_tag("0.7.9-beta.1")),
+                Bump::Custom(ref pre) => {
+                    NextVersion::Updated(version_tag(&format!("0.7.9-{pre}.1")))
                 }
-                Bump::Beta => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-beta.1", "v").unwrap())
-                }
-                Bump::Custom(ref pre) => NextVersion::Updated(
-                    VersionTag::parse(format!("v0.7.9-{pre}.1").as_str(), "v").unwrap(),
-                ),
                 Bump::Rc | Bump::Patch | Bump::Minor | Bump::Major => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-rc.2", "v").unwrap())
+                    NextVersion::Updated(version_tag("0.7.9-rc.2"))
                 }
-                Bump::Release => NextVersion::Updated(VersionTag::parse("v0.7.9", "v").unwrap()),
-                Bump::First => NextVersion::Updated(VersionTag::parse("v1.0.0", "v").unwrap()),
+                Bump::Release => NextVersion::Updated(version_tag("0.7.9")),
+                Bump::First => NextVersion::Updated(version_tag("1.0.0")),
             },
             "refs/tags/v0.7.9-pre.1" => match bump {
-                Bump::None => NextVersion::Updated(VersionTag::parse("v0.7.9-pre.1", "v").unwrap()),
-                Bump::Alpha => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-alpha.1", "v").unwrap())
-                }
-                Bump::Beta => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-beta.1", "v").unwrap())
-                }
-                Bump::Rc => NextVersion::Updated(VersionTag::parse("v0.7.9-rc.1", "v").unwrap()),
+                Bump::None => NextVersion::Updated(version_tag("0.7.9-pre.1")),
+                Bump::Alpha => NextVersion::Updated(version_tag("0.7.9-alpha.1")),
+                Bump::Beta => NextVersion::Updated(version_tag("0.7.9-beta.1")),
+                Bump::Rc => NextVersion::Updated(version_tag("0.7.9-rc.1")),
                 Bump::Custom(_) | Bump::Patch | Bump::Minor | Bump::Major => {
-                    NextVersion::Updated(VersionTag::parse("v0.7.9-pre.2", "v").unwrap())
+                    NextVersion::Updated(version_tag("0.7.9-pre.2"))
                 }
-                Bump::Release => NextVersion::Updated(VersionTag::parse("v0.7.9", "v").unwrap()),
-                Bump::First => NextVersion::Updated(VersionTag::parse("v1.0.0", "v").unwrap()),
+                Bump::Release => NextVersion::Updated(version_tag("0.7.9")),
+                Bump::First => NextVersion::Updated(version_tag("1.0.0")),
             },
             _ => unreachable!("unexpected tag"),
         };
@@ -0,0 +1,312 @@
+//! Attribution of the files changed by a commit to a package
+//!
+
+use std::path::{Component, Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+use crate::Error;
+
+/// Globs adding files to, or removing files from, the files of a package and
+/// the commit scopes mapped to the package
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub(crate) struct PackagePaths {
+    /// Files outside the package directory that count for the package
+    pub(crate) include: Vec<String>,
+    /// Files that do not count for the package
+    pub(crate) exclude: Vec<String>,
+    /// Commit scopes that count for the package whatever files are changed
+    pub(crate) scopes: Vec<String>,
+}
+
+/// Decides if the files changed by a commit belong to a package.
+///
+/// A file belongs to the package if it is in the package directory, matches
+/// one of the include globs or, unless turned off, is a manifest or lock file
+/// in the root of the repository. Files matching an exclude glob never belong
+/// to the package.
+///
+/// Globs containing a `/` are matched against the path from the root of the
+/// repository; other globs are matched against the file name.
+///
+/// A commit with one of the scopes mapped to the package also belongs to the
+/// package.
+#[derive(Debug, Clone)]
+pub(crate) struct PathFilter {
+    prefix: PathBuf,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    root_files: bool,
+    scopes: Vec<String>,
+}
+
+impl PathFilter {
+    /// Create the filter for the package directory `prefix`, relative to the
+    /// root of the repository.
+    pub(crate) fn new(
+        prefix: &str,
+        paths: Option<&PackagePaths>,
+        root_files: bool,
+    ) -> Result<Self, Error> {
+        let prefix = Path::new(prefix)
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect();
+        let paths = paths.cloned().unwrap_or_default();
+
+        Ok(PathFilter {
+            prefix,
+            include: compile(&paths.include)?,
+            exclude: compile(&paths.exclude)?,
+            root_files,
+            scopes: paths
+                .scopes
+                .iter()
+                .map(|scope| scope.to_lowercase())
+                .collect(),
+        })
+    }
+
+    /// The package directory matched by the filter
+    pub(crate) fn prefix(&self) -> String {
+        self.prefix.to_string_lossy().to_string()
+    }
+
+    /// True if any of the files belongs to the package.
+    pub(crate) fn qualifies(&self, files: &[PathBuf]) -> bool {
+        log::debug!("checking files against the filter: {self:?}");
+        let qualified_files: Vec<_> = files.iter().filter(|file| self.matches(file)).collect();
+        log::debug!("qualified files: `{qualified_files:#?}`");
+
+        !qualified_files.is_empty()
+    }
+
+    /// True if any of the commit scopes is mapped to the package.
+    pub(crate) fn has_scope(&self, scopes: &[String]) -> bool {
+        scopes.iter().any(|scope| self.scopes.contains(scope))
+    }
+
+    fn matches(&self, file: &Path) -> bool {
+        if self
+            .exclude
+            .iter()
+            .any(|pattern| glob_matches(pattern, file))
+        {
+            return false;
+        }
+
+        file.starts_with(&self.prefix)
+            || self
+                .include
+                .iter()
+                .any(|pattern| glob_matches(pattern, file))
+            || (self.root_files && is_root_file(file))
+    }
+}
+
+/// Selects the changed files considered in the calculation.
+///
+/// If include globs are set only the files matching one of them are considered.
+/// Files matching an exclude glob are not considered. The globs are matched as
+/// for the [`PathFilter`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PathGlobs {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl PathGlobs {
+    pub(crate) fn new(include: &[String], exclude: &[String]) -> Result<Self, Error> {
+        Ok(PathGlobs {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// The files that are considered in the calculation.
+    pub(crate) fn select(&self, files: &[PathBuf]) -> Vec<PathBuf> {
+        files
+            .iter()
+            .filter(|file| {
+                (self.include.is_empty()
+                    || self
+                        .include
+                        .iter()
+                        .any(|pattern| glob_matches(pattern, file)))
+                    && !self
+                        .exclude
+                        .iter()
+                        .any(|pattern| glob_matches(pattern, file))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+fn compile(globs: &[String]) -> Result<Vec<Pattern>, Error> {
+    globs.iter().map(|glob| Ok(Pattern::new(glob)?)).collect()
+}
+
+fn glob_matches(pattern: &Pattern, file: &Path) -> bool {
+    if pattern.as_str().contains('/') {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        pattern.matches_path_with(file, options)
+    } else {
+        file.file_name()
+            .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
+    }
+}
+
+/// True if the file is a manifest or lock file in the root of the repository.
+fn is_root_file(file: &Path) -> bool {
+    file.components().count() == 1
+        && file
+            .extension()
+            .is_some_and(|extension| extension == "toml" || extension == "lock")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+
+    use super::{PackagePaths, PathFilter, PathGlobs};
+
+    #[rstest]
+    #[case::in_package("crates/core/src/lib.rs", true)]
+    #[case::package_manifest("crates/core/Cargo.toml", true)]
+    #[case::similar_directory("crates/hardcore/src/lib.rs", false)]
+    #[case::similar_file("docs/core.md", false)]
+    #[case::prefix_of_name("crates/core-utils/src/lib.rs", false)]
+    #[case::root_manifest("Cargo.toml", true)]
+    #[case::root_lock("Cargo.lock", true)]
+    #[case::root_readme("README.md", false)]
+    #[case::nested_manifest("crates/other/Cargo.toml", false)]
+    fn test_prefix(#[case] file: &str, #[case] expected: bool) {
+        let filter = PathFilter::new("crates/core", None, true).unwrap();
+
+        assert_eq!(expected, filter.qualifies(&[PathBuf::from(file)]));
+    }
+
+    #[rstest]
+    #[case::plain("core")]
+    #[case::current_dir("./core")]
+    #[case::trailing_slash("core/")]
+    fn test_prefix_is_normalised(#[case] prefix: &str) {
+        let filter = PathFilter::new(prefix, None, false).unwrap();
+
+        assert!(filter.qualifies(&[PathBuf::from("core/src/lib.rs")]));
+        assert!(!filter.qualifies(&[PathBuf::from("hardcore/src/lib.rs")]));
+        assert_eq!("core", filter.prefix());
+    }
+
+    #[rstest]
+    #[case::root_manifest("Cargo.toml")]
+    #[case::root_lock("Cargo.lock")]
+    fn test_root_files_ignored(#[case] file: &str) {
+        let filter = PathFilter::new("crates/core", None, false).unwrap();
+
+        assert!(!filter.qualifies(&[PathBuf::from(file)]));
+    }
+
+    #[rstest]
+    #[case::included_path("proto/core.proto", true)]
+    #[case::included_other_path("proto/app.proto", true)]
+    #[case::not_included("docs/core.md", false)]
+    #[case::excluded_path("crates/core/tests/data/big.json", false)]
+    #[case::excluded_name("crates/core/CHANGELOG.md", false)]
+    #[case::excluded_root_file("Cargo.lock", false)]
+    #[case::still_in_package("crates/core/tests/api.rs", true)]
+    fn test_globs(#[case] file: &str, #[case] expected: bool) {
+        let paths = PackagePaths {
+            include: vec!["proto/*.proto".to_string()],
+            exclude: vec![
+                "crates/core/tests/data/**".to_string(),
+                "CHANGELOG.md".to_string(),
+                "Cargo.lock".to_string(),
+            ],
+            scopes: vec![],
+        };
+        let filter = PathFilter::new("crates/core", Some(&paths), true).unwrap();
+
+        assert_eq!(expected, filter.qualifies(&[PathBuf::from(file)]));
+    }
+
+    #[test]
+    fn test_any_file_qualifies() {
+        let filter = PathFilter::new("crates/core", None, false).unwrap();
+
+        assert!(filter.qualifies(&[
+            PathBuf::from("docs/core.md"),
+            PathBuf::from("crates/core/src/lib.rs"),
+        ]));
+    }
+
+    #[test]
+    fn test_invalid_glob() {
+        let paths = PackagePaths {
+            include: vec!["src/[".to_string()],
+            exclude: vec![],
+            scopes: vec![],
+        };
+
+        assert!(PathFilter::new("crates/core", Some(&paths), true).is_err());
+    }
+
+    #[rstest]
+    #[case::mapped(&["parser"], true)]
+    #[case::one_of_several(&["docs", "lexer"], true)]
+    #[case::not_mapped(&["docs"], false)]
+    #[case::no_scope(&[], false)]
+    fn test_has_scope(#[case] scopes: &[&str], #[case] expected: bool) {
+        let paths = PackagePaths {
+            scopes: vec!["Parser".to_string(), "lexer".to_string()],
+            ..Default::default()
+        };
+        let filter = PathFilter::new("crates/core", Some(&paths), true).unwrap();
+
+        let scopes: Vec<_> = scopes.iter().map(|scope| scope.to_string()).collect();
+        assert_eq!(expected, filter.has_scope(&scopes));
+    }
+
+    #[rstest]
+    #[case::no_globs(
+        &[],
+        &[],
+        &["docs/guide.md", "src/lib.rs", "src/generated/api.rs", "Cargo.toml"]
+    )]
+    #[case::exclude(
+        &[],
+        &["docs/**", "*.md"],
+        &["src/lib.rs", "src/generated/api.rs", "Cargo.toml"]
+    )]
+    #[case::include(
+        &["src/**", "Cargo.toml"],
+        &[],
+        &["src/lib.rs", "src/generated/api.rs", "Cargo.toml"]
+    )]
+    #[case::include_and_exclude(&["src/**"], &["src/generated/**"], &["src/lib.rs"])]
+    #[case::everything_excluded(&[], &["*"], &[])]
+    fn test_select(#[case] include: &[&str], #[case] exclude: &[&str], #[case] expected: &[&str]) {
+        let to_strings = |globs: &[&str]| {
+            globs
+                .iter()
+                .map(|glob| glob.to_string())
+                .collect::<Vec<_>>()
+        };
+        let globs = PathGlobs::new(&to_strings(include), &to_strings(exclude)).unwrap();
+        let files = [
+            PathBuf::from("docs/guide.md"),
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("src/generated/api.rs"),
+            PathBuf::from("Cargo.toml"),
+        ];
+
+        let expected: Vec<_> = expected.iter().map(PathBuf::from).collect();
+        assert_eq!(expected, globs.select(&files));
+    }
+}
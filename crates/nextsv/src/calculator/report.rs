@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::Hierarchy;
+
+/// Structured report of the calculation.
+///
+/// Created by calling the [`Calculator::structured_report`](crate::Calculator::structured_report)
+/// method. The report can be serialised, e.g. to JSON, for consumption by other tools.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
+pub struct Report {
+    /// The semantic version found in the current version tag
+    pub current_version: String,
+    /// The name of the current version tag
+    pub current_tag: String,
+    /// True if the current version tag is an annotated tag
+    pub current_tag_annotated: bool,
+    /// The tagger of the current version tag, if it is an annotated tag
+    pub current_tag_tagger: Option<String>,
+    /// The date of the current version tag, or of the tagged commit for a lightweight tag
+    pub current_tag_date: Option<String>,
+    /// The message of the current version tag, if it is an annotated tag
+    pub current_tag_message: Option<String>,
+    /// The next version number, if a change is required
+    pub next_version: Option<String>,
+    /// The name of the tag for the next version, if a change is required
+    pub next_tag: Option<String>,
+    /// The bump applied to calculate the next version
+    pub bump: String,
+    /// The level of change indicated by the commits (major, minor, patch or none)
+    pub change_bump: String,
+    /// The route taken for the calculation based on the current version
+    pub route: String,
+    /// The highest level of change found in the commits
+    pub top_type: Hierarchy,
+    /// The number of commits found for each commit type
+    pub commit_counts: BTreeMap<String, u32>,
+    /// True if a breaking change was found
+    pub breaking: bool,
+    /// Commits detected as major version dependency bumps
+    pub major_dep_bumps: Vec<String>,
+    /// Required files that were not updated
+    pub missing_files: Vec<String>,
+}
+
+impl Report {
+    /// Returns true if the calculation requires a new release.
+    pub fn released(&self) -> bool {
+        self.next_version.is_some()
+    }
+
+    /// Format the key results as `key=value` lines.
+    ///
+    /// The format is suitable for appending to the file named by `$GITHUB_OUTPUT`
+    /// in GitHub Actions or for a GitLab dotenv report. The keys are `bump`,
+    /// `version`, `tag`, `released` and `previous_version`. `version` and `tag`
+    /// are empty when no release is required.
+    ///
+    /// # Example
+    ///
+    /// ```console
+    /// bump=minor
+    /// version=1.2.0
+    /// tag=v1.2.0
+    /// released=true
+    /// previous_version=1.1.0
+    /// ```
+    pub fn to_env(&self) -> String {
+        format!(
+            "bump={}\nversion={}\ntag={}\nreleased={}\nprevious_version={}\n",
+            self.bump,
+            self.next_version.as_deref().unwrap_or_default(),
+            self.next_tag.as_deref().unwrap_or_default(),
+            self.released(),
+            self.current_version,
+        )
+    }
+}
+
+/// Structured report of the calculation for a package of a workspace.
+///
+/// Created by calling the [`WorkspaceCalculator::structured_report`](crate::WorkspaceCalculator::structured_report)
+/// method.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
+pub struct PackageReport {
+    /// The name of the package
+    pub package: String,
+    /// The directory of the package in the workspace
+    pub member: String,
+    /// Released workspace dependencies from which the package inherited a bump
+    pub inherited_from: Vec<String>,
+    /// True if commits since the version tag changed files of the package
+    pub changed: bool,
+    /// The report of the calculation for the package
+    #[serde(flatten)]
+    pub report: Report,
+}
+
+impl PackageReport {
+    /// Format the key results as `key=value` lines with each key prefixed by
+    /// the package name, e.g. `my_crate_bump=minor` for the package `my-crate`.
+    ///
+    /// Characters of the package name that are not valid in a variable name
+    /// are replaced by `_`.
+    pub fn to_env(&self) -> String {
+        let prefix: String = self
+            .package
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.report
+            .to_env()
+            .lines()
+            .map(|line| format!("{prefix}_{line}\n"))
+            .collect()
+    }
+}
+
+/// Report of a version of the version history.
+///
+/// Created by calling the [`VersionHistory::structured_report`](crate::VersionHistory::structured_report)
+/// method.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
+pub struct ReleaseReport {
+    /// The name of the version tag
+    pub tag: String,
+    /// The semantic version of the tag
+    pub version: String,
+    /// The id of the tagged commit
+    pub commit: String,
+    /// The date of the tag, or of the tagged commit for a lightweight tag
+    pub date: Option<String>,
+    /// The previous version in precedence order, if any
+    pub previous_version: Option<String>,
+    /// The level of change released, from the commits since the previous
+    /// version (major, minor, patch or none), or `first` for the first version
+    pub bump: String,
+    /// The highest level of change found in the commits since the previous version
+    pub top_type: Hierarchy,
+    /// The number of commits counted since the previous version
+    pub commits: usize,
+}
+
+/// Report of a tag of the repository checked as a version tag.
+///
+/// Created by calling the [`TagList::structured_report`](crate::TagList::structured_report)
+/// method.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
+pub struct TagReport {
+    /// The name of the tag
+    pub tag: String,
+    /// The semantic version of the tag, if it is accepted as a version tag
+    pub version: Option<String>,
+    /// `current` for the current version tag, else `accepted` or `rejected`
+    pub status: String,
+    /// Why the tag was accepted or rejected
+    pub reason: String,
+}
+
+/// Format rows as a plain text table below the headers.
+///
+/// Every column but the last is padded to its widest cell and columns are
+/// separated by two spaces.
+pub(crate) fn format_table<const N: usize>(headers: [&str; N], rows: Vec<[String; N]>) -> String {
+    let rows = std::iter::once(headers.map(String::from))
+        .chain(rows)
+        .collect::<Vec<_>>();
+
+    let mut widths = [0; N];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            let mut line = String::new();
+            for (i, (cell, width)) in row.iter().zip(widths).enumerate() {
+                if i + 1 < N {
+                    line.push_str(&format!("{cell:<width$}  "));
+                } else {
+                    line.push_str(cell);
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{format_table, PackageReport, Report};
+
+    #[rstest]
+    #[case::released(
+        Some("1.2.0"),
+        Some("v1.2.0"),
+        "minor",
+        "bump=minor\nversion=1.2.0\ntag=v1.2.0\nreleased=true\nprevious_version=1.1.0\n"
+    )]
+    #[case::not_released(
+        None,
+        None,
+        "none",
+        "bump=none\nversion=\ntag=\nreleased=false\nprevious_version=1.1.0\n"
+    )]
+    fn test_to_env(
+        #[case] next_version: Option<&str>,
+        #[case] next_tag: Option<&str>,
+        #[case] bump: &str,
+        #[case] expected: &str,
+    ) {
+        let report = Report {
+            current_version: "1.1.0".to_string(),
+            current_tag: "v1.1.0".to_string(),
+            next_version: next_version.map(String::from),
+            next_tag: next_tag.map(String::from),
+            bump: bump.to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(expected, report.to_env());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![
+            [
+                "v1.0.0".to_string(),
+                "accepted".to_string(),
+                "ok".to_string(),
+            ],
+            ["v10.0.0-rc.1".to_string(), "-".to_string(), "".to_string()],
+        ];
+
+        let expected = "tag           status    reason\n\
+                        v1.0.0        accepted  ok\n\
+                        v10.0.0-rc.1  -         ";
+        assert_eq!(expected, format_table(["tag", "status", "reason"], rows));
+    }
+
+    #[rstest]
+    #[case::simple_name(
+        "one",
+        "one_bump=none\none_version=\none_tag=\none_released=false\none_previous_version=0.1.0\n"
+    )]
+    #[case::hyphenated_name(
+        "my-crate",
+        "my_crate_bump=none\nmy_crate_version=\nmy_crate_tag=\nmy_crate_released=false\nmy_crate_previous_version=0.1.0\n"
+    )]
+    fn test_package_to_env(#[case] package: &str, #[case] expected: &str) {
+        let report = PackageReport {
+            package: package.to_string(),
+            member: "crates/one".to_string(),
+            inherited_from: vec![],
+            changed: false,
+            report: Report {
+                current_version: "0.1.0".to_string(),
+                bump: "none".to_string(),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(expected, report.to_env());
+    }
+}
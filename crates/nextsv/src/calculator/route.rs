@@ -60,7 +60,7 @@ mod test {
         #[case] pre_release: &str,
         #[case] expected: Route,
     ) {
-        let version = Semantic::new(major, minor, patch, pre_release, "");
+        let version = Semantic::new(major, minor, patch, pre_release, "").unwrap();
 
         let test = Route::calculate(&version);
 
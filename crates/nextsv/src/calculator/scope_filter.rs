@@ -0,0 +1,70 @@
+//! Selection of commits by their conventional commit scope
+//!
+
+/// Selects the commits considered in the calculation by their scopes.
+///
+/// If include scopes are set only commits with one of the scopes are
+/// considered. Commits where every scope is excluded are not considered.
+/// Scopes are compared in lowercase.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ScopeFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl ScopeFilter {
+    pub(crate) fn new(include: &[String], exclude: &[String]) -> Self {
+        let lowercase =
+            |scopes: &[String]| scopes.iter().map(|scope| scope.to_lowercase()).collect();
+
+        ScopeFilter {
+            include: lowercase(include),
+            exclude: lowercase(exclude),
+        }
+    }
+
+    /// True if a commit with the scopes is considered in the calculation.
+    pub(crate) fn selects(&self, scopes: &[String]) -> bool {
+        if !scopes.is_empty() && scopes.iter().all(|scope| self.exclude.contains(scope)) {
+            return false;
+        }
+
+        self.include.is_empty() || scopes.iter().any(|scope| self.include.contains(scope))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::ScopeFilter;
+
+    #[rstest]
+    #[case::no_filter(&[], &[], &["parser"], true)]
+    #[case::no_filter_no_scope(&[], &[], &[], true)]
+    #[case::excluded(&[], &["docs"], &["docs"], false)]
+    #[case::excluded_case_insensitive(&[], &["Docs"], &["docs"], false)]
+    #[case::not_excluded(&[], &["docs"], &["parser"], true)]
+    #[case::partly_excluded(&[], &["docs"], &["docs", "parser"], true)]
+    #[case::no_scope_not_excluded(&[], &["docs"], &[], true)]
+    #[case::included(&["parser", "lexer"], &[], &["lexer"], true)]
+    #[case::not_included(&["parser"], &[], &["lexer"], false)]
+    #[case::no_scope_not_included(&["parser"], &[], &[], false)]
+    #[case::included_and_excluded(&["parser"], &["parser"], &["parser"], false)]
+    fn test_selects(
+        #[case] include: &[&str],
+        #[case] exclude: &[&str],
+        #[case] scopes: &[&str],
+        #[case] expected: bool,
+    ) {
+        let to_strings = |scopes: &[&str]| {
+            scopes
+                .iter()
+                .map(|scope| scope.to_string())
+                .collect::<Vec<_>>()
+        };
+        let filter = ScopeFilter::new(&to_strings(include), &to_strings(exclude));
+
+        assert_eq!(expected, filter.selects(&to_strings(scopes)));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    version::{TagRejection, VersionTag},
+    CalculatorConfig, Error, TagSelection,
+};
+
+use super::{get_subdir_for_package, report::format_table, TagReport};
+
+/// Lists every tag of the repository with the reason it was accepted or
+/// rejected as a version tag.
+///
+/// Created by calling the [`CalculatorConfig::build_tag_list`] method.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct TagList {
+    tags: Vec<TagReport>,
+}
+
+impl TagList {
+    /// Check every tag of the repository against the prefix, or tag template,
+    /// of the package and mark the tag taken as the current version.
+    ///
+    /// This method is typically executed by [`CalculatorConfig::build_tag_list`].
+    pub(crate) fn execute(config: CalculatorConfig) -> Result<Self, Error> {
+        let (repo, workdir) = config.open_repository()?;
+
+        let subdir = get_subdir_for_package(
+            config.repository_dir(),
+            &workdir,
+            config.package.as_deref(),
+            config.subdir.as_deref(),
+        )?;
+        let template = config.tag_template(
+            config.package.as_deref(),
+            subdir.as_deref().unwrap_or_default(),
+        )?;
+
+        let checked = VersionTag::check_tags(
+            &repo,
+            &template,
+            config.tag_selection,
+            config.to.as_deref(),
+            config.annotated_only,
+        )?;
+        let versions = checked
+            .iter()
+            .filter_map(|(_, outcome)| outcome.as_ref().ok().cloned())
+            .collect();
+        let current = VersionTag::select_current(versions, config.tag_selection, &template)
+            .map(|version| version.tag_name());
+
+        let tags = checked
+            .into_iter()
+            .map(|(tag, outcome)| match outcome {
+                Ok(version) if current.as_ref() == Some(&tag) => TagReport {
+                    version: Some(version.semantic_version.to_string()),
+                    status: "current".to_string(),
+                    reason: format!(
+                        "current version tag by the `{}` selection",
+                        config.tag_selection
+                    ),
+                    tag,
+                },
+                Ok(version) => TagReport {
+                    version: Some(version.semantic_version.to_string()),
+                    status: "accepted".to_string(),
+                    reason: "version tag".to_string(),
+                    tag,
+                },
+                Err(rejection) => TagReport {
+                    version: None,
+                    status: "rejected".to_string(),
+                    reason: match rejection {
+                        TagRejection::NoMatch => format!("does not match `{template}`"),
+                        TagRejection::InvalidVersion(e) => format!("invalid version: {e}"),
+                        TagRejection::Lightweight => {
+                            "lightweight tag; only annotated tags are considered".to_string()
+                        }
+                        TagRejection::NotSelected(TagSelection::Nearest) => {
+                            "not on the nearest tagged commit".to_string()
+                        }
+                        TagRejection::NotSelected(_) => {
+                            "not reachable from the start commit".to_string()
+                        }
+                    },
+                    tag,
+                },
+            })
+            .collect();
+
+        Ok(TagList { tags })
+    }
+
+    /// Report the tags as a table.
+    ///
+    /// # Example
+    ///
+    /// ```console
+    /// tag        version  status    reason
+    /// initial    -        rejected  does not match `v{version}`
+    /// v1.0.0     1.0.0    accepted  version tag
+    /// v1.1.0     1.1.0    current   current version tag by the `highest` selection
+    /// v1.2       -        rejected  invalid version: Version must have three components but only 2 found
+    /// ```
+    pub fn report(&self) -> String {
+        let rows = self
+            .tags
+            .iter()
+            .map(|tag| {
+                [
+                    tag.tag.clone(),
+                    tag.version.clone().unwrap_or_else(|| "-".to_string()),
+                    tag.status.clone(),
+                    tag.reason.clone(),
+                ]
+            })
+            .collect();
+
+        format_table(["tag", "version", "status", "reason"], rows)
+    }
+
+    /// Create a structured report for each tag of the repository.
+    pub fn structured_report(&self) -> Vec<TagReport> {
+        self.tags.clone()
+    }
+}
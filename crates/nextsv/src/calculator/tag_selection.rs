@@ -0,0 +1,36 @@
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// The `TagSelection` enum decides which version tag is taken as the current
+/// version.
+///
+/// Only the tags reachable from the commit where the calculation starts are
+/// considered by default, so that a maintenance branch is not compared with
+/// a later release made on another branch.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagSelection {
+    /// The highest version tag reachable from the starting commit.
+    #[default]
+    Highest,
+    /// The version tag nearest to the starting commit, following its ancestors.
+    Nearest,
+    /// The highest version tag in the repository, reachable or not.
+    Any,
+    /// The most recent version tag reachable from the starting commit, by the
+    /// date of an annotated tag or the commit date of a lightweight tag.
+    Latest,
+}
+
+impl fmt::Display for TagSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagSelection::Highest => write!(f, "highest"),
+            TagSelection::Nearest => write!(f, "nearest"),
+            TagSelection::Any => write!(f, "any"),
+            TagSelection::Latest => write!(f, "latest"),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     cmp,
+    collections::HashMap,
     fmt::{self, Display},
 };
 
@@ -26,6 +27,30 @@ impl TopType {
             _ => Self::Other,
         })
     }
+
+    /// The lowercase name of the level, matching the [`Hierarchy`] names
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Breaking => "breaking",
+            Self::Feature => "feature",
+            Self::Fix => "fix",
+            Self::Other => "other",
+            Self::None => "none",
+        }
+    }
+
+    /// Resolve the level of a commit type, using the user supplied
+    /// `type_levels` before falling back to the default mapping.
+    pub(crate) fn for_type(commit_type: &str, type_levels: &HashMap<String, Hierarchy>) -> Self {
+        match type_levels.get(&commit_type.to_lowercase()) {
+            Some(Hierarchy::Breaking) => Self::Breaking,
+            Some(Hierarchy::Feature) => Self::Feature,
+            Some(Hierarchy::Fix) => Self::Fix,
+            Some(Hierarchy::Other) => Self::Other,
+            Some(Hierarchy::None) => Self::None,
+            None => Self::parse(commit_type).unwrap_or(Self::Other),
+        }
+    }
 }
 
 impl Display for TopType {
@@ -47,6 +72,7 @@ impl From<&Hierarchy> for TopType {
             Hierarchy::Feature => Self::Feature,
             Hierarchy::Fix => Self::Fix,
             Hierarchy::Other => Self::Fix,
+            Hierarchy::None => Self::None,
         }
     }
 }
@@ -0,0 +1,137 @@
+use crate::{version::VersionTag, CalculatorConfig, Error, Hierarchy, TagSelection};
+
+use super::{
+    get_subdir_for_package, report::format_table, ChangeBump, ConventionalCommits, ReleaseReport,
+};
+
+/// Lists the version tags of a package in precedence order with the level of
+/// change released by each version.
+///
+/// Created by calling the [`CalculatorConfig::build_history`] method.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct VersionHistory {
+    releases: Vec<ReleaseReport>,
+}
+
+impl VersionHistory {
+    /// Find every version tag of the prefix, or tag template, of the package
+    /// and walk the commits between consecutive versions to find the level of
+    /// change each release represented.
+    ///
+    /// All the version tags are listed irrespective of the tag selection; if
+    /// only annotated tags are considered lightweight tags are left out.
+    ///
+    /// This method is typically executed by [`CalculatorConfig::build_history`].
+    pub(crate) fn execute(config: CalculatorConfig) -> Result<Self, Error> {
+        let (repo, workdir) = config.open_repository()?;
+
+        let subdir = get_subdir_for_package(
+            config.repository_dir(),
+            &workdir,
+            config.package.as_deref(),
+            config.subdir.as_deref(),
+        )?;
+        let template = config.tag_template(
+            config.package.as_deref(),
+            subdir.as_deref().unwrap_or_default(),
+        )?;
+        let filter = match &subdir {
+            Some(subdir) => Some(config.path_filter(config.package.as_deref(), subdir)?),
+            None => None,
+        };
+
+        let mut versions: Vec<VersionTag> = VersionTag::check_tags(
+            &repo,
+            &template,
+            TagSelection::Any,
+            None,
+            config.annotated_only,
+        )?
+        .into_iter()
+        .filter_map(|(_, outcome)| outcome.ok())
+        .collect();
+        versions.sort();
+
+        let mut releases = vec![];
+        let mut previous: Option<VersionTag> = None;
+        for version in versions {
+            let commit = match repo
+                .revparse_single(&version.to_string())
+                .and_then(|object| object.peel_to_commit())
+            {
+                Ok(commit) => commit.id(),
+                Err(e) => {
+                    log::warn!("Skipping version tag `{version}` as it does not tag a commit: {e}");
+                    continue;
+                }
+            };
+
+            let conventional = ConventionalCommits::walk_back_commits_to_tag_reference(
+                &repo,
+                previous
+                    .as_ref()
+                    .map(|version| version.to_string())
+                    .as_deref(),
+                Some(&version.to_string()),
+                filter.as_ref(),
+                &config.path_globs()?,
+                &config.scope_filter(),
+                &config.type_levels,
+            )?;
+            let bump = match &previous {
+                Some(previous) => {
+                    ChangeBump::calculate(&previous.semantic_version, &conventional).to_string()
+                }
+                None => "first".to_string(),
+            };
+            releases.push(ReleaseReport {
+                tag: version.tag_name(),
+                version: version.semantic_version.to_string(),
+                commit: commit.to_string(),
+                date: version.date_string(),
+                previous_version: previous
+                    .as_ref()
+                    .map(|previous| previous.semantic_version.to_string()),
+                bump,
+                top_type: Hierarchy::from(&conventional.top_type),
+                commits: conventional.commits.len(),
+            });
+            previous = Some(version);
+        }
+
+        Ok(VersionHistory { releases })
+    }
+
+    /// Report the versions as a table, lowest version first.
+    ///
+    /// # Example
+    ///
+    /// ```console
+    /// tag     commit   date                       bump   commits
+    /// v1.0.0  3f2a91c  2024-03-01 10:12:45 +0000  first  12
+    /// v1.1.0  8be04d7  2024-04-15 16:03:10 +0000  minor  5
+    /// v1.1.1  c41d0e2  2024-04-20 09:30:00 +0000  patch  1
+    /// ```
+    pub fn report(&self) -> String {
+        let rows = self
+            .releases
+            .iter()
+            .map(|release| {
+                [
+                    release.tag.clone(),
+                    release.commit.chars().take(7).collect(),
+                    release.date.clone().unwrap_or_else(|| "-".to_string()),
+                    release.bump.clone(),
+                    release.commits.to_string(),
+                ]
+            })
+            .collect();
+
+        format_table(["tag", "commit", "date", "bump", "commits"], rows)
+    }
+
+    /// Create a structured report for each version, lowest version first.
+    pub fn structured_report(&self) -> Vec<ReleaseReport> {
+        self.releases.clone()
+    }
+}
@@ -0,0 +1,380 @@
+use std::path::Path;
+
+use git2::Repository;
+
+use crate::{
+    version::VersionTag, workspace::Package, CalculatorConfig, Error, Hierarchy, Workspace,
+};
+
+use super::{report::format_table, Bump, Calculator, ConventionalCommits, PackageReport, TopType};
+
+/// Used to calculate the bump and next version for every package in a workspace.
+///
+/// Created by calling the [`CalculatorConfig::build_workspace`] method.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct WorkspaceCalculator {
+    packages: Vec<PackageCalculation>,
+}
+
+/// The calculation for a single package of the workspace
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct PackageCalculation {
+    name: String,
+    member: String,
+    /// Workspace packages that this package depends on
+    dependencies: Vec<String>,
+    /// Commits found for the package before any inherited level is applied
+    conventional: ConventionalCommits,
+    /// Released dependencies from which the package inherited a bump
+    inherited_from: Vec<String>,
+    /// True if commits since the tag changed files of the package
+    changed: bool,
+    calculator: Calculator,
+}
+
+impl WorkspaceCalculator {
+    /// Find the version tag for each package of the workspace and walk back
+    /// through the commits once to calculate the next version of every package.
+    ///
+    /// The tag for a package is identified by the prefix `<package-name>-v`, or
+    /// by the tag template with the name and directory of the package.
+    /// For packages without a version tag all the commits from the root commit
+    /// are collected and the first version is reported as the next version.
+    ///
+    /// In lockstep mode the calculation is made once for the whole workspace.
+    /// See [`CalculatorConfig::set_lockstep`].
+    ///
+    /// This method is typically executed by [`CalculatorConfig::build_workspace`].
+    pub(crate) fn execute(config: CalculatorConfig) -> Result<Self, Error> {
+        let (repo, workdir) = config.open_repository()?;
+
+        let workspace = Workspace::discover(config.repository_dir())?;
+        let packages = workspace.packages()?;
+
+        if config.lockstep {
+            return WorkspaceCalculator::execute_lockstep(
+                &repo, &workspace, &workdir, config, packages,
+            );
+        }
+
+        let mut tagged = vec![];
+        for package in packages {
+            let member = workspace.member_path(&package.member, &workdir);
+            let template = config.tag_template(Some(&package.name), &member)?;
+            match VersionTag::find_in_repo(
+                &repo,
+                &template,
+                config.tag_selection,
+                None,
+                config.annotated_only,
+            ) {
+                Ok(current_version) => {
+                    let tag = repo
+                        .find_reference(&current_version.to_string())?
+                        .peel_to_commit()?
+                        .id();
+                    tagged.push((package, member, template, Some(current_version), Some(tag)));
+                }
+                Err(Error::NoVersionTag) => {
+                    log::info!(
+                        "No version tag found for package `{}` so the first version is calculated",
+                        package.name
+                    );
+                    tagged.push((package, member, template, None, None));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let members = tagged
+            .iter()
+            .map(|(package, member, _, _, tag)| {
+                Ok((config.path_filter(Some(&package.name), member)?, *tag))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let all_commits = ConventionalCommits::walk_back_workspace(
+            &repo,
+            &members,
+            &config.path_globs()?,
+            &config.scope_filter(),
+            &config.type_levels,
+        )?;
+
+        let names: Vec<String> = tagged
+            .iter()
+            .map(|(package, _, _, _, _)| package.name.clone())
+            .collect();
+        let mut calculations = vec![];
+        for ((package, _, template, current_version, _), conventional) in
+            tagged.into_iter().zip(all_commits)
+        {
+            log::debug!(
+                "Calculating the next version for package `{}`",
+                package.name
+            );
+            let package_config = config.clone().set_package(Some(&package.name));
+            let calculator = match current_version {
+                Some(current_version) => {
+                    Calculator::calculate(package_config, current_version, conventional.clone())?
+                }
+                None => {
+                    let first_version = package_config.member_first_version(
+                        &repo,
+                        &workspace.root.join(&package.member),
+                        &template,
+                    )?;
+                    Calculator::first_release(package_config, first_version, conventional.clone())
+                }
+            };
+            calculations.push(PackageCalculation {
+                name: package.name,
+                member: package.member,
+                dependencies: package
+                    .dependencies
+                    .into_iter()
+                    .filter(|dependency| names.contains(dependency))
+                    .collect(),
+                changed: calculator.first_release || !conventional.commits.is_empty(),
+                conventional,
+                inherited_from: vec![],
+                calculator,
+            });
+        }
+
+        let mut workspace = WorkspaceCalculator {
+            packages: calculations,
+        };
+        workspace.propagate(&config)?;
+
+        Ok(workspace)
+    }
+
+    /// Find the version tag shared by the workspace and walk back through the
+    /// commits of all the members to calculate a single next version that
+    /// applies to every package.
+    ///
+    /// Without a shared version tag the first version is reported for every
+    /// package.
+    fn execute_lockstep(
+        repo: &Repository,
+        workspace: &Workspace,
+        workdir: &Path,
+        config: CalculatorConfig,
+        packages: Vec<Package>,
+    ) -> Result<Self, Error> {
+        let template = config.tag_template(None, "")?;
+        let current_version = match VersionTag::find_in_repo(
+            repo,
+            &template,
+            config.tag_selection,
+            None,
+            config.annotated_only,
+        ) {
+            Ok(current_version) => {
+                log::debug!("Shared version tag for the workspace is `{current_version}`");
+                Some(current_version)
+            }
+            Err(Error::NoVersionTag) => {
+                log::info!("No shared version tag found so the first version is calculated");
+                None
+            }
+            Err(e) => return Err(e),
+        };
+
+        let members = packages
+            .iter()
+            .map(|package| {
+                let member = workspace.member_path(&package.member, workdir);
+                config.path_filter(Some(&package.name), &member)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let (conventional, changed) = ConventionalCommits::walk_back_lockstep(
+            repo,
+            current_version
+                .as_ref()
+                .map(|version| version.to_string())
+                .as_deref(),
+            &members,
+            &config.path_globs()?,
+            &config.scope_filter(),
+            &config.type_levels,
+        )?;
+
+        let calculator = match current_version {
+            Some(current_version) => {
+                Calculator::calculate(config, current_version, conventional.clone())?
+            }
+            None => {
+                let first_version = config.first_version(repo, &workspace.root, &template)?;
+                Calculator::first_release(config, first_version, conventional.clone())
+            }
+        };
+
+        let packages = packages
+            .into_iter()
+            .zip(changed)
+            .map(|(package, changed)| PackageCalculation {
+                name: package.name,
+                member: package.member,
+                dependencies: vec![],
+                conventional: conventional.clone(),
+                inherited_from: vec![],
+                changed: calculator.first_release || changed,
+                calculator: calculator.clone(),
+            })
+            .collect();
+
+        Ok(WorkspaceCalculator { packages })
+    }
+
+    /// Keep only the packages changed by commits since their version tag.
+    pub fn changed_only(mut self) -> Self {
+        self.packages.retain(|package| package.changed);
+        self
+    }
+
+    /// Propagate the bumps of released packages to the packages that depend on them.
+    ///
+    /// A package depending on a released workspace package inherits the dependency
+    /// level set in the config, or a breaking change if the dependency has a
+    /// breaking change and is re-exported by the package. The propagation is
+    /// repeated until no further packages are affected so that bumps pass along
+    /// chains of dependencies.
+    fn propagate(&mut self, config: &CalculatorConfig) -> Result<(), Error> {
+        let dependency_level = config.dependency_level.clone().unwrap_or(Hierarchy::Fix);
+        if dependency_level == Hierarchy::None {
+            log::debug!("Propagation of bumps to dependent packages is turned off");
+            return Ok(());
+        }
+
+        // Each pass can only release more packages so the number of passes is bounded.
+        for _ in 0..=self.packages.len() {
+            let mut changed = false;
+            for i in 0..self.packages.len() {
+                let mut level = TopType::None;
+                let mut inherited_from = vec![];
+                for dependency in &self.packages[i].dependencies {
+                    let Some(released) = self.packages.iter().find(|package| {
+                        &package.name == dependency && package.calculator.bump != Bump::None
+                    }) else {
+                        continue;
+                    };
+                    let re_exported = config
+                        .re_exports
+                        .get(&self.packages[i].name)
+                        .is_some_and(|re_exports| re_exports.contains(dependency));
+                    let dependency_type =
+                        if re_exported && released.calculator.conventional.breaking {
+                            TopType::Breaking
+                        } else {
+                            TopType::from(&dependency_level)
+                        };
+                    level = level.max(dependency_type);
+                    inherited_from.push(dependency.clone());
+                }
+
+                let package = &mut self.packages[i];
+                if package.calculator.first_release || inherited_from == package.inherited_from {
+                    continue;
+                }
+                changed = true;
+                log::debug!(
+                    "Package `{}` inherits `{level}` from {inherited_from:?}",
+                    package.name
+                );
+                let mut conventional = package.conventional.clone();
+                if !inherited_from.is_empty() {
+                    conventional.inherit(level.clone(), &inherited_from);
+                }
+                let package_config = config.clone().set_package(Some(&package.name));
+                let mut calculator = Calculator::calculate(
+                    package_config,
+                    package.calculator.current_version.clone(),
+                    conventional,
+                )?;
+                if !inherited_from.is_empty() {
+                    calculator.decisions.insert(
+                        1,
+                        format!(
+                            "Level `{}` inherited from the release of {}",
+                            level.name(),
+                            inherited_from.join(", ")
+                        ),
+                    );
+                }
+                package.calculator = calculator;
+                package.inherited_from = inherited_from;
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Report the current version, bump and next version of each package as a table.
+    ///
+    /// # Example
+    ///
+    /// ```console
+    /// package  current  bump   next
+    /// one      1.1.0    minor  1.2.0
+    /// two      0.3.1    none   -
+    /// three    0.2.4    patch  0.2.5 (inherited from one)
+    /// ```
+    pub fn report(&self) -> String {
+        let reports = self.structured_report();
+
+        let mut rows = vec![];
+        for report in reports {
+            rows.push([
+                report.package,
+                match report.report.current_version.as_str() {
+                    "" => "-".to_string(),
+                    current_version => current_version.to_string(),
+                },
+                report.report.bump,
+                match report.report.next_version {
+                    Some(next_version) if !report.inherited_from.is_empty() => format!(
+                        "{next_version} (inherited from {})",
+                        report.inherited_from.join(", ")
+                    ),
+                    Some(next_version) => next_version,
+                    None => "-".to_string(),
+                },
+            ]);
+        }
+
+        format_table(["package", "current", "bump", "next"], rows)
+    }
+
+    /// Advisory warnings raised by the calculations of the packages, each listed once.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for warning in self
+            .packages
+            .iter()
+            .flat_map(|package| package.calculator.warnings())
+        {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+        warnings
+    }
+
+    /// Create a structured report for each package of the workspace.
+    pub fn structured_report(&self) -> Vec<PackageReport> {
+        self.packages
+            .iter()
+            .map(|package| PackageReport {
+                package: package.name.clone(),
+                member: package.member.clone(),
+                inherited_from: package.inherited_from.clone(),
+                changed: package.changed,
+                report: package.calculator.structured_report(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,356 @@
+//! Project configuration loaded from `nextsv.toml` and `Cargo.toml`
+//!
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use cargo_toml::Manifest;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Hierarchy, TagSelection};
+
+/// Name of the nextsv configuration file at the root of the repository
+pub const CONFIG_FILE_NAME: &str = "nextsv.toml";
+
+/// Settings for the calculation that are read from the project files.
+///
+/// The settings are collected, lowest precedence first, from:
+/// 1. `[workspace.metadata.nextsv]` in `Cargo.toml`
+/// 2. `[package.metadata.nextsv]` in `Cargo.toml`
+/// 3. `nextsv.toml`
+///
+/// Settings that are not found are `None` and leave the calculator default,
+/// or the value set on the command line, in place.
+///
+/// # Example
+///
+/// ```toml
+/// prefix = "v"
+/// check = "fix"
+/// tag-selection = "nearest"
+/// initial-version = "0.1.0"
+/// required-files = ["CHANGELOG.md"]
+/// enforce-level = "feature"
+/// no-release = ["docs", "ci"]
+/// dependency-level = "fix"
+/// ignore-root-files = true
+/// exclude-paths = ["docs/**", ".github/**", "*.md"]
+/// exclude-scopes = ["release"]
+///
+/// [type-levels]
+/// perf = "fix"
+/// security = "fix"
+///
+/// [re-exports]
+/// app = ["core"]
+///
+/// [packages.core]
+/// include = ["proto/**"]
+/// exclude = ["CHANGELOG.md"]
+/// scopes = ["core", "parser"]
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ConfigFile {
+    /// Prefix string to identify version number tags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Filter to commits in the specified sub directory only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// Select package from workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// Files that must be updated before making the release
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_files: Option<Vec<String>>,
+    /// Level at which the required files are enforced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_level: Option<Hierarchy>,
+    /// Threshold level that must be met before the bump is reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check: Option<Hierarchy>,
+    /// Levels assigned to commit types
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub type_levels: BTreeMap<String, Hierarchy>,
+    /// Commit types that do not require a release
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_release: Option<Vec<String>>,
+    /// Level inherited by a workspace package when a dependency is released
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency_level: Option<Hierarchy>,
+    /// Workspace dependencies re-exported by each package
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub re_exports: BTreeMap<String, Vec<String>>,
+    /// Release all workspace packages together with a shared version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lockstep: Option<bool>,
+    /// Globs for the paths considered in the calculation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_paths: Option<Vec<String>>,
+    /// Globs for the paths not considered in the calculation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_paths: Option<Vec<String>>,
+    /// Commit scopes considered in the calculation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_scopes: Option<Vec<String>>,
+    /// Commit scopes not considered in the calculation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_scopes: Option<Vec<String>>,
+    /// Files and scopes that count for each package of the workspace
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub packages: BTreeMap<String, PackageConfig>,
+    /// Do not count changes to manifest and lock files in the repository root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_root_files: Option<bool>,
+    /// Version tag taken as the current version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_selection: Option<TagSelection>,
+    /// Version to release first when no version tag is found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_version: Option<String>,
+    /// Only consider annotated version tags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotated_only: Option<bool>,
+    /// Template for the names of the version tags, e.g. `{package}@{version}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_template: Option<String>,
+}
+
+/// Settings for a single package, set in the `[packages.<name>]` table.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct PackageConfig {
+    /// Globs for files outside the package directory that count for the package
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Globs for files that do not count for the package
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Commit scopes that count for the package
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+}
+
+/// The `metadata` tables of `Cargo.toml`, keeping only the `nextsv` key.
+#[derive(Debug, Default, Deserialize)]
+struct CargoMetadata {
+    #[serde(default)]
+    nextsv: Option<ConfigFile>,
+}
+
+impl ConfigFile {
+    /// Load and merge the configuration found in the directory.
+    ///
+    /// Missing files are not an error; an empty configuration is returned if
+    /// no settings are found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::{CalculatorConfig, ConfigFile};
+    ///     let config_file = ConfigFile::load(Path::new("."))?;
+    ///
+    ///     let calculator = CalculatorConfig::new()
+    ///         .set_prefix("v")
+    ///         .apply_config_file(&config_file)
+    ///         .build()?;
+    ///
+    ///     calculator.report();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load(dir: &Path) -> Result<Self, Error> {
+        let mut config = ConfigFile::default();
+
+        let cargo_toml = dir.join("Cargo.toml");
+        if cargo_toml.is_file() {
+            log::debug!("Reading nextsv metadata from `{}`", cargo_toml.display());
+            let manifest = Manifest::<CargoMetadata>::from_path_with_metadata(&cargo_toml)?;
+            if let Some(nextsv) = manifest
+                .workspace
+                .and_then(|workspace| workspace.metadata)
+                .and_then(|metadata| metadata.nextsv)
+            {
+                config = config.merge(nextsv);
+            }
+            if let Some(nextsv) = manifest
+                .package
+                .and_then(|package| package.metadata)
+                .and_then(|metadata| metadata.nextsv)
+            {
+                config = config.merge(nextsv);
+            }
+        }
+
+        let config_file = dir.join(CONFIG_FILE_NAME);
+        if config_file.is_file() {
+            log::debug!("Reading configuration from `{}`", config_file.display());
+            let content = fs::read_to_string(&config_file)?;
+            config = config.merge(Self::parse(&content)?);
+        }
+
+        log::debug!("Configuration loaded from files: {config:?}");
+
+        Ok(config)
+    }
+
+    /// Parse the configuration from a TOML string.
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Merge `other` over `self`. Settings in `other` take precedence;
+    /// type levels, re-exports and package settings are combined.
+    pub fn merge(mut self, other: ConfigFile) -> Self {
+        self.prefix = other.prefix.or(self.prefix);
+        self.subdir = other.subdir.or(self.subdir);
+        self.package = other.package.or(self.package);
+        self.required_files = other.required_files.or(self.required_files);
+        self.enforce_level = other.enforce_level.or(self.enforce_level);
+        self.check = other.check.or(self.check);
+        self.type_levels.extend(other.type_levels);
+        self.no_release = other.no_release.or(self.no_release);
+        self.dependency_level = other.dependency_level.or(self.dependency_level);
+        self.re_exports.extend(other.re_exports);
+        self.lockstep = other.lockstep.or(self.lockstep);
+        self.include_paths = other.include_paths.or(self.include_paths);
+        self.exclude_paths = other.exclude_paths.or(self.exclude_paths);
+        self.include_scopes = other.include_scopes.or(self.include_scopes);
+        self.exclude_scopes = other.exclude_scopes.or(self.exclude_scopes);
+        self.packages.extend(other.packages);
+        self.ignore_root_files = other.ignore_root_files.or(self.ignore_root_files);
+        self.tag_selection = other.tag_selection.or(self.tag_selection);
+        self.initial_version = other.initial_version.or(self.initial_version);
+        self.annotated_only = other.annotated_only.or(self.annotated_only);
+        self.tag_template = other.tag_template.or(self.tag_template);
+        self
+    }
+
+    /// Format the configuration as TOML in the layout of `nextsv.toml`.
+    pub fn to_toml(&self) -> Result<String, Error> {
+        Ok(toml::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::rstest;
+
+    use super::{ConfigFile, PackageConfig};
+    use crate::{Hierarchy, TagSelection};
+
+    #[test]
+    fn test_parse_all_settings() {
+        let content = r#"
+prefix = "release-"
+subdir = "crates/one"
+package = "one"
+required-files = ["CHANGELOG.md", "README.md"]
+enforce-level = "breaking"
+check = "fix"
+no-release = ["docs", "ci"]
+dependency-level = "feature"
+lockstep = true
+ignore-root-files = true
+tag-selection = "nearest"
+initial-version = "0.1.0"
+annotated-only = true
+tag-template = "{package}@{version}"
+include-paths = ["src/**", "Cargo.toml"]
+exclude-paths = ["*.md"]
+include-scopes = ["core", "parser"]
+exclude-scopes = ["docs"]
+
+[type-levels]
+perf = "fix"
+deprecate = "feature"
+
+[re-exports]
+app = ["core"]
+
+[packages.core]
+include = ["proto/**"]
+exclude = ["CHANGELOG.md"]
+scopes = ["parser"]
+"#;
+
+        let expected = ConfigFile {
+            prefix: Some("release-".to_string()),
+            subdir: Some("crates/one".to_string()),
+            package: Some("one".to_string()),
+            required_files: Some(vec!["CHANGELOG.md".to_string(), "README.md".to_string()]),
+            enforce_level: Some(Hierarchy::Breaking),
+            check: Some(Hierarchy::Fix),
+            type_levels: BTreeMap::from([
+                ("perf".to_string(), Hierarchy::Fix),
+                ("deprecate".to_string(), Hierarchy::Feature),
+            ]),
+            no_release: Some(vec!["docs".to_string(), "ci".to_string()]),
+            dependency_level: Some(Hierarchy::Feature),
+            re_exports: BTreeMap::from([("app".to_string(), vec!["core".to_string()])]),
+            lockstep: Some(true),
+            packages: BTreeMap::from([(
+                "core".to_string(),
+                PackageConfig {
+                    include: vec!["proto/**".to_string()],
+                    exclude: vec!["CHANGELOG.md".to_string()],
+                    scopes: vec!["parser".to_string()],
+                },
+            )]),
+            ignore_root_files: Some(true),
+            tag_selection: Some(TagSelection::Nearest),
+            initial_version: Some("0.1.0".to_string()),
+            annotated_only: Some(true),
+            tag_template: Some("{package}@{version}".to_string()),
+            include_paths: Some(vec!["src/**".to_string(), "Cargo.toml".to_string()]),
+            exclude_paths: Some(vec!["*.md".to_string()]),
+            include_scopes: Some(vec!["core".to_string(), "parser".to_string()]),
+            exclude_scopes: Some(vec!["docs".to_string()]),
+        };
+
+        assert_eq!(expected, ConfigFile::parse(content).unwrap());
+    }
+
+    #[rstest]
+    #[case::unknown_key("prefixes = \"v\"")]
+    #[case::unknown_level("check = \"minor\"")]
+    #[case::wrong_type("required-files = \"README.md\"")]
+    #[case::unknown_package_key("[packages.core]\nfiles = [\"src/**\"]")]
+    fn test_parse_rejects_invalid(#[case] content: &str) {
+        assert!(ConfigFile::parse(content).is_err());
+    }
+
+    #[test]
+    fn test_merge_other_takes_precedence() {
+        let base = ConfigFile::parse(
+            "prefix = \"v\"\ncheck = \"fix\"\n[type-levels]\nperf = \"fix\"\ndocs = \"other\"",
+        )
+        .unwrap();
+        let other =
+            ConfigFile::parse("prefix = \"app-v\"\n[type-levels]\ndocs = \"none\"").unwrap();
+
+        let merged = base.merge(other);
+
+        assert_eq!(Some("app-v".to_string()), merged.prefix);
+        assert_eq!(Some(Hierarchy::Fix), merged.check);
+        assert_eq!(Some(&Hierarchy::Fix), merged.type_levels.get("perf"));
+        assert_eq!(Some(&Hierarchy::None), merged.type_levels.get("docs"));
+    }
+
+    #[test]
+    fn test_to_toml_round_trip() {
+        let config = ConfigFile::parse(
+            "prefix = \"v\"\nno-release = [\"ci\"]\n[type-levels]\nperf = \"fix\"",
+        )
+        .unwrap();
+
+        let toml = config.to_toml().unwrap();
+
+        assert_eq!(config, ConfigFile::parse(&toml).unwrap());
+        assert!(!toml.contains("subdir"));
+    }
+}
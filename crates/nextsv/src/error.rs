@@ -28,6 +28,9 @@ pub enum Error {
     /// The component must be a digit
     #[error("Version must be a number but found {0}")]
     MustBeNumber(String),
+    /// The component is too large for a version number
+    #[error("Version component {0} is too large")]
+    ComponentOutOfRange(String),
     /// No valid version tag was found in the repository
     #[error("No valid version tag found in the repository")]
     NoVersionTag,
@@ -61,6 +64,30 @@ pub enum Error {
     // /// The minimum change level set for check has not been met.
     // #[error("Minimum change level has not been met.")]
     // MinimumChangeLevelNotMet,
+    /// The manifest does not define a workspace with members.
+    #[error("No workspace members found in `Cargo.toml`")]
+    NotWorkspace,
+    /// The repository is bare so there are no manifests to read.
+    #[error("The repository at `{0}` has no working directory")]
+    BareRepository(String),
+    /// No version number was found in the manifest to update.
+    #[error("No version found to update in the manifest `{0}`")]
+    NoManifestVersion(String),
+    /// The tag template does not contain a single `{version}` placeholder.
+    #[error("The tag template `{0}` must contain `{{version}}` exactly once")]
+    TagTemplate(String),
+    /// The version is not a valid semantic version.
+    #[error("`{0}` is not a valid semantic version")]
+    InvalidVersion(String),
+    /// The tag for the next version already exists in the repository.
+    #[error("The tag `{0}` for the next version already exists")]
+    TagExists(String),
+    /// The tag for the first version already exists in the repository.
+    #[error("The tag `{0}` for the first version already exists")]
+    FirstVersionTagExists(String),
+    /// Error passed up from toml_edit when editing a manifest
+    #[error("manifest edit error says: {0}")]
+    ManifestEdit(#[from] toml_edit::TomlError),
     /// Error passed up from git2
     #[error("0:?")]
     Git2(#[from] git2::Error),
@@ -70,6 +97,18 @@ pub enum Error {
     /// Error passed up from cargo_toml
     #[error("cargo_toml error says: {0:?}")]
     Toml(#[from] cargo_toml::Error),
+    /// Error passed up from toml when reading the configuration file
+    #[error("configuration file error says: {0}")]
+    ConfigFile(#[from] toml::de::Error),
+    /// Error passed up from toml when writing the configuration
+    #[error("configuration format error says: {0}")]
+    ConfigFormat(#[from] toml::ser::Error),
+    /// Error passed up from std::io
+    #[error("io error says: {0}")]
+    Io(#[from] std::io::Error),
+    /// Error passed up from glob when a workspace member pattern is invalid
+    #[error("workspace member pattern error says: {0}")]
+    Glob(#[from] glob::PatternError),
     /// Error passed up from regex
     #[error("regex error says: {0:?}")]
     Regex(#[from] regex::Error),
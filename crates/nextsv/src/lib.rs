@@ -97,13 +97,19 @@
 //! ```
 
 mod calculator;
+mod config_file;
 mod error;
 #[cfg(test)]
 mod test_utils;
 mod version;
 mod workspace;
 
-pub use calculator::{Calculator, CalculatorConfig, ForceBump, Hierarchy};
+pub use calculator::{
+    Applied, ApplyConfig, Calculator, CalculatorConfig, FileChange, Footer, ForceBump, Hierarchy,
+    PackageReport, ParsedCommit, ReleaseReport, Report, TagList, TagReport, TagSelection,
+    VersionHistory, WorkspaceCalculator,
+};
+pub use config_file::{ConfigFile, PackageConfig, CONFIG_FILE_NAME};
 pub use error::Error;
 pub use workspace::Workspace;
 // pub use version::VersionTag;
@@ -1,8 +1,15 @@
-use std::ffi::OsString;
+use std::{
+    ffi::OsString,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
-use clap::{Parser, Subcommand};
-use nextsv::{CalculatorConfig, ForceBump, Hierarchy};
-use proc_exit::{Code, ExitResult};
+use clap::{Parser, Subcommand, ValueEnum};
+use nextsv::{
+    ApplyConfig, CalculatorConfig, ConfigFile, Error, ForceBump, Hierarchy, TagSelection,
+};
+use proc_exit::{Code, Exit, ExitResult};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -18,6 +25,9 @@ struct Cli {
     /// Report the version number
     #[arg(short = 'n', long)]
     number: bool,
+    /// Report the name of the tag for the next version, e.g. `nextsv-v0.19.36`
+    #[arg(short = 't', long)]
+    tag: bool,
 
     /// Check level meets minimum for setting
     ///
@@ -26,6 +36,140 @@ struct Cli {
     /// as "none" if the required level is not met.
     #[clap(short, long)]
     check: Option<Hierarchy>,
+
+    /// Assign a commit type to a level
+    ///
+    /// Overrides the default level for the commit type, e.g.
+    /// `--type-level perf=fix --type-level docs=none`. Types assigned
+    /// to `none` do not require a release.
+    #[arg(long, value_name = "TYPE=LEVEL", value_parser = parse_type_level)]
+    type_level: Vec<(String, Hierarchy)>,
+
+    /// Commit types that do not require a release
+    ///
+    /// Commits of these types are ignored when calculating the bump so
+    /// that, for example, `--no-release docs,ci` reports "none" when
+    /// only documentation and CI changes have been made. Replaces the
+    /// list set in the config file.
+    #[arg(long, value_name = "TYPE", value_delimiter = ',')]
+    no_release: Vec<String>,
+
+    /// Do not count changes to root manifest and lock files
+    ///
+    /// By default a change to a `*.toml` or `*.lock` file in the root
+    /// of the repository counts as a change to every package.
+    #[arg(long, overrides_with = "no_ignore_root_files")]
+    ignore_root_files: bool,
+
+    /// Count changes to root manifest and lock files, overriding the config file
+    #[arg(long, overrides_with = "ignore_root_files")]
+    no_ignore_root_files: bool,
+
+    /// Only consider commits changing paths that match the glob
+    ///
+    /// Globs containing a `/` are matched against the path from the
+    /// root of the repository, e.g. `src/**`; other globs are matched
+    /// against the file name, e.g. `Cargo.toml`. Replaces the globs
+    /// set in the config file.
+    #[arg(long, value_name = "GLOB")]
+    include_path: Vec<String>,
+
+    /// Skip commits that only change paths matching the glob
+    ///
+    /// For example `--exclude-path docs/** --exclude-path *.md` skips
+    /// commits that only change documentation. Replaces the globs set
+    /// in the config file.
+    #[arg(long, value_name = "GLOB")]
+    exclude_path: Vec<String>,
+
+    /// Only consider commits with one of the scopes
+    ///
+    /// For example `--include-scope parser,lexer` considers
+    /// `feat(parser): ...` and skips `feat(cli): ...` and commits
+    /// without a scope. Replaces the scopes set in the config file.
+    #[arg(long, value_name = "SCOPE", value_delimiter = ',')]
+    include_scope: Vec<String>,
+
+    /// Skip commits with the scopes, replacing those set in the config file
+    #[arg(long, value_name = "SCOPE", value_delimiter = ',')]
+    exclude_scope: Vec<String>,
+
+    /// Version tag taken as the current version [default: highest]
+    ///
+    /// Only the tags reachable from the commit where the calculation
+    /// starts are considered, unless `any` is selected.
+    #[arg(long, value_enum, value_name = "SELECTION")]
+    tag_selection: Option<TagSelection>,
+
+    /// Only consider annotated version tags
+    ///
+    /// Lightweight tags, e.g. created by `git tag v1.2.3` without a
+    /// message, are ignored when finding the current version.
+    #[arg(long, overrides_with = "no_annotated_only")]
+    annotated_only: bool,
+
+    /// Consider lightweight version tags, overriding the config file
+    #[arg(long, overrides_with = "annotated_only")]
+    no_annotated_only: bool,
+
+    /// Version to release first when no version tag is found
+    ///
+    /// Without a version tag all commits from the root commit are
+    /// counted and the first version is reported. If not set the first
+    /// version is only reported for a repository without any tags, using
+    /// the version in Cargo.toml, or 0.1.0 if there is no manifest.
+    #[arg(long, value_name = "VERSION")]
+    initial_version: Option<String>,
+
+    /// Template for the names of the version tags, e.g. `{package}@{version}`
+    ///
+    /// Used to find the current version tag and to name the next tag.
+    /// `{package}` and `{path}` are replaced by the package name and
+    /// directory. Replaces the prefix when set.
+    #[arg(long, value_name = "TEMPLATE")]
+    tag_template: Option<String>,
+
+    /// Path to the git repository
+    ///
+    /// Any directory in the repository may be given; the repository is
+    /// found by searching the directory and its parents. Defaults to
+    /// the current directory.
+    #[arg(long, value_name = "PATH")]
+    repo: Option<PathBuf>,
+
+    /// Print the effective configuration and exit
+    ///
+    /// Settings are read from `[workspace.metadata.nextsv]` and
+    /// `[package.metadata.nextsv]` in Cargo.toml and from nextsv.toml,
+    /// with command line options taking precedence. The result is
+    /// printed in the format of nextsv.toml.
+    #[arg(long)]
+    print_config: bool,
+
+    /// Format of the report
+    ///
+    /// The json format reports the full calculation, including the
+    /// current version, route, commit counts and any missing files,
+    /// irrespective of the --no-bump and --number options.
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// File to append the env format output to
+    ///
+    /// Defaults to the file named by the GITHUB_OUTPUT environment
+    /// variable when it is set; otherwise the output is printed.
+    #[arg(long, value_name = "PATH")]
+    output_file: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// Bump and/or version number on separate lines
+    Text,
+    /// JSON object describing the calculation
+    Json,
+    /// `key=value` lines for $GITHUB_OUTPUT or a dotenv file
+    Env,
 }
 
 #[derive(Subcommand, Debug)]
@@ -39,14 +183,65 @@ enum Commands {
         about = "Require the listed files to be updated before making a release with the specified change level"
     )]
     Require(Require),
+    #[clap(
+        name = "explain",
+        about = "Explain how the next version was calculated from the commits"
+    )]
+    Explain(Calculate),
+    #[clap(
+        name = "apply",
+        about = "Write the next version to the manifests, commit the change and create the tag"
+    )]
+    Apply(Apply),
+    #[clap(
+        name = "workspace",
+        about = "Calculate the next version for every package in the workspace"
+    )]
+    Workspace(WorkspaceArgs),
+    #[clap(
+        name = "tags",
+        about = "List every tag and why it was accepted or rejected as a version tag"
+    )]
+    Tags(Tags),
+    #[clap(
+        name = "list",
+        about = "List the version history with the bump released by each version"
+    )]
+    List(List),
 }
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Calculate {
-    /// Prefix string to identify version number tags
-    #[arg(short, long, value_parser, default_value = "v")]
-    prefix: String,
+    /// Prefix string to identify version number tags [default: v]
+    #[arg(short, long, value_parser)]
+    prefix: Option<String>,
+    /// Filter to commits in the specified sub directory only
+    #[arg(short, long)]
+    subdir: Option<String>,
+    /// Select package from workspace. Overrides --prefix and --subdir
+    ///
+    /// Equivalent to setting:
+    ///     --prefix <package-name> --subdir <package-dir>
+    #[clap(short = 'k', long)]
+    pub package: Option<String>,
+    /// Count the commits from this revision [default: the current version tag]
+    ///
+    /// Any revision understood by git may be given, such as a tag,
+    /// branch or commit id. A version tag is used as the current version.
+    #[arg(long, value_name = "REV")]
+    from: Option<String>,
+    /// Count the commits up to this revision [default: HEAD]
+    #[arg(long, value_name = "REV")]
+    to: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Apply {
+    /// Prefix string to identify version number tags [default: v]
+    #[arg(short, long, value_parser)]
+    prefix: Option<String>,
     /// Filter to commits in the specified sub directory only
     #[arg(short, long)]
     subdir: Option<String>,
@@ -56,6 +251,71 @@ struct Calculate {
     ///     --prefix <package-name> --subdir <package-dir>
     #[clap(short = 'k', long)]
     pub package: Option<String>,
+    /// Template for the release commit message; `{version}` and `{tag}` are replaced
+    #[arg(short, long, default_value = "chore: release {version}")]
+    message: String,
+    /// Show the changes without writing, committing or tagging
+    #[arg(short, long)]
+    dry_run: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Tags {
+    /// Prefix string to identify version number tags [default: v]
+    #[arg(short, long, value_parser)]
+    prefix: Option<String>,
+    /// Filter to commits in the specified sub directory only
+    #[arg(short, long)]
+    subdir: Option<String>,
+    /// Select package from workspace. Overrides --prefix and --subdir
+    ///
+    /// Equivalent to setting:
+    ///     --prefix <package-name> --subdir <package-dir>
+    #[clap(short = 'k', long)]
+    pub package: Option<String>,
+    /// Select the current version tag from this revision [default: HEAD]
+    #[arg(long, value_name = "REV")]
+    to: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct List {
+    /// Prefix string to identify version number tags [default: v]
+    #[arg(short, long, value_parser)]
+    prefix: Option<String>,
+    /// Filter to commits in the specified sub directory only
+    #[arg(short, long)]
+    subdir: Option<String>,
+    /// Select package from workspace. Overrides --prefix and --subdir
+    ///
+    /// Equivalent to setting:
+    ///     --prefix <package-name> --subdir <package-dir>
+    #[clap(short = 'k', long)]
+    pub package: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct WorkspaceArgs {
+    /// Level inherited by a package when a workspace dependency is released, or
+    /// `none` to not inherit [default: fix]
+    #[arg(long, value_name = "LEVEL", value_parser = parse_level)]
+    dependency_level: Option<Hierarchy>,
+    /// Package re-exports the types of a workspace dependency, so it inherits
+    /// the breaking changes of the dependency
+    #[arg(long, value_name = "PACKAGE=DEPENDENCY", value_parser = parse_re_export)]
+    re_export: Vec<(String, String)>,
+    /// Release every package together with the version of the shared tag
+    #[arg(long, overrides_with = "no_lockstep")]
+    lockstep: bool,
+    /// Release each package with its own version, overriding the config file
+    #[arg(long, overrides_with = "lockstep")]
+    no_lockstep: bool,
+    /// Report only the packages changed since their version tag
+    #[arg(long)]
+    changed: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -67,9 +327,9 @@ struct Force {
     /// First flag to set first version and pre-release in the same transaction
     #[arg(short, long)]
     first: bool,
-    /// Prefix string to identify version number tags
-    #[arg(short, long, value_parser, default_value = "v")]
-    prefix: String,
+    /// Prefix string to identify version number tags [default: v]
+    #[arg(short, long, value_parser)]
+    prefix: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -77,11 +337,12 @@ struct Force {
 struct Require {
     #[command(subcommand)]
     enforce: Hierarchy,
+    /// Files that must be updated, replacing those set in the config file
     #[arg(short, long)]
     files: Vec<OsString>,
-    /// Prefix string to identify version number tags
-    #[arg(short, long, value_parser, default_value = "v")]
-    prefix: String,
+    /// Prefix string to identify version number tags [default: v]
+    #[arg(short, long, value_parser)]
+    prefix: Option<String>,
     /// Filter to commits in the specified sub directory only
     #[arg(short, long)]
     subdir: Option<String>,
@@ -111,44 +372,299 @@ fn run() -> ExitResult {
         (true, true) => log::info!("Calculating the next version number and level"),
     };
 
-    let mut calculator_config = CalculatorConfig::new();
+    let repo_dir = args.repo.as_deref().unwrap_or(Path::new("."));
+    let config_file =
+        ConfigFile::load(&repository_workdir(repo_dir))?.merge(command_line_config(&args));
+
+    let mut calculator_config = CalculatorConfig::new()
+        .set_prefix("v")
+        .apply_config_file(&config_file);
+    if let Some(repo) = &args.repo {
+        calculator_config = calculator_config.set_repository_path(repo);
+    }
+    if let Some(selection) = args.tag_selection {
+        calculator_config = calculator_config.set_tag_selection(selection);
+    }
+    if args.initial_version.is_some() {
+        calculator_config = calculator_config.set_initial_version(args.initial_version.as_deref());
+    }
+    if args.tag_template.is_some() {
+        calculator_config = calculator_config.set_tag_template(args.tag_template.as_deref());
+    }
     calculator_config = calculator_config.set_bump_report(!args.no_bump);
     calculator_config = calculator_config.set_version_report(args.number);
+    calculator_config = calculator_config.set_tag_report(args.tag);
 
     if let Some(check_level) = args.check {
         calculator_config = calculator_config.set_reporting_threshold(check_level);
     };
 
+    for (commit_type, level) in args.type_level {
+        calculator_config = calculator_config.set_type_level(&commit_type, level);
+    }
+
+    let explain = matches!(args.command, Commands::Explain(_));
+    let workspace = matches!(args.command, Commands::Workspace(_));
+    let tags = matches!(args.command, Commands::Tags(_));
+    let list = matches!(args.command, Commands::List(_));
+    let mut changed_only = false;
+    let mut apply_config = None;
+
     match args.command {
         Commands::Force(args) => {
-            calculator_config = calculator_config.set_prefix(&args.prefix);
+            if let Some(prefix) = args.prefix {
+                calculator_config = calculator_config.set_prefix(&prefix);
+            };
             calculator_config = calculator_config.set_force_bump(args.bump);
             if args.first {
                 log::debug!("Setting first version and pre-release in the same transaction");
                 calculator_config = calculator_config.set_first_version();
             };
         }
-        Commands::Calculate(args) => {
-            calculator_config = calculator_config.set_prefix(&args.prefix);
-            calculator_config = calculator_config.set_subdir(args.subdir.as_deref());
-            calculator_config = calculator_config.set_package(args.package.as_deref());
+        Commands::Calculate(args) | Commands::Explain(args) => {
+            calculator_config =
+                set_selection(calculator_config, args.prefix, args.subdir, args.package);
+            calculator_config = calculator_config
+                .set_from(args.from.as_deref())
+                .set_to(args.to.as_deref());
+        }
+        Commands::Apply(args) => {
+            calculator_config =
+                set_selection(calculator_config, args.prefix, args.subdir, args.package);
+            apply_config = Some(
+                ApplyConfig::new()
+                    .set_message(&args.message)
+                    .set_dry_run(args.dry_run),
+            );
+        }
+        Commands::Workspace(args) => {
+            if let Some(level) = args.dependency_level {
+                calculator_config = calculator_config.set_dependency_level(level);
+            }
+            for (package, dependency) in args.re_export {
+                calculator_config = calculator_config.add_re_export(&package, &dependency);
+            }
+            changed_only = args.changed;
+        }
+        Commands::Tags(args) => {
+            calculator_config =
+                set_selection(calculator_config, args.prefix, args.subdir, args.package);
+            calculator_config = calculator_config.set_to(args.to.as_deref());
+        }
+        Commands::List(args) => {
+            calculator_config =
+                set_selection(calculator_config, args.prefix, args.subdir, args.package);
         }
         Commands::Require(args) => {
-            calculator_config = calculator_config.set_prefix(&args.prefix);
-            calculator_config = calculator_config.set_subdir(args.subdir.as_deref());
-            calculator_config = calculator_config.set_package(args.package.as_deref());
-            calculator_config = calculator_config.add_required_files(args.files);
+            calculator_config =
+                set_selection(calculator_config, args.prefix, args.subdir, args.package);
             calculator_config = calculator_config.set_required_enforcement(args.enforce);
         }
     };
 
+    if args.print_config {
+        print!("{}", calculator_config.config_file().to_toml()?);
+        return Code::SUCCESS.ok();
+    }
+
+    if tags {
+        let tags = calculator_config.build_tag_list()?;
+        match args.output {
+            OutputFormat::Json => {
+                let report = serde_json::to_string_pretty(&tags.structured_report())
+                    .map_err(|e| Exit::new(Code::FAILURE).with_message(e.to_string()))?;
+                println!("{report}");
+            }
+            OutputFormat::Text | OutputFormat::Env => println!("{}", tags.report()),
+        };
+        return Code::SUCCESS.ok();
+    }
+
+    if list {
+        let history = calculator_config.build_history()?;
+        match args.output {
+            OutputFormat::Json => {
+                let report = serde_json::to_string_pretty(&history.structured_report())
+                    .map_err(|e| Exit::new(Code::FAILURE).with_message(e.to_string()))?;
+                println!("{report}");
+            }
+            OutputFormat::Text | OutputFormat::Env => println!("{}", history.report()),
+        };
+        return Code::SUCCESS.ok();
+    }
+
+    if workspace {
+        let mut workspace = calculator_config.build_workspace()?;
+        if changed_only {
+            workspace = workspace.changed_only();
+        }
+        print_warnings(&workspace.warnings());
+        match args.output {
+            OutputFormat::Text => println!("{}", workspace.report()),
+            OutputFormat::Json => {
+                let report = serde_json::to_string_pretty(&workspace.structured_report())
+                    .map_err(|e| Exit::new(Code::FAILURE).with_message(e.to_string()))?;
+                println!("{report}");
+            }
+            OutputFormat::Env => {
+                let report: String = workspace
+                    .structured_report()
+                    .iter()
+                    .map(|report| report.to_env())
+                    .collect();
+                write_env(&report, args.output_file)?;
+            }
+        };
+        return Code::SUCCESS.ok();
+    }
+
     let calculator = calculator_config.build()?;
+    print_warnings(&calculator.warnings());
 
-    println!("{}", calculator.report());
+    if explain {
+        println!("{}", calculator.explain());
+        return Code::SUCCESS.ok();
+    }
+
+    if let Some(apply_config) = apply_config {
+        match calculator.apply(&apply_config)? {
+            Some(applied) => println!("{applied}"),
+            None => println!("No release required"),
+        }
+        return Code::SUCCESS.ok();
+    }
+
+    match args.output {
+        OutputFormat::Text => println!("{}", calculator.report()),
+        OutputFormat::Json => {
+            let report = serde_json::to_string_pretty(&calculator.structured_report())
+                .map_err(|e| Exit::new(Code::FAILURE).with_message(e.to_string()))?;
+            println!("{report}");
+        }
+        OutputFormat::Env => write_env(&calculator.structured_report().to_env(), args.output_file)?,
+    };
 
     Code::SUCCESS.ok()
 }
 
+/// The working directory of the repository containing `dir`, where the
+/// configuration files are found, or `dir` if it is not in a repository.
+fn repository_workdir(dir: &Path) -> PathBuf {
+    git2::Repository::discover(dir)
+        .ok()
+        .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+        .unwrap_or_else(|| dir.to_path_buf())
+}
+
+/// The settings given on the command line, merged over the config file so
+/// that lists replace the lists of the file and flags can turn settings off.
+fn command_line_config(args: &Cli) -> ConfigFile {
+    let list = |values: &[String]| (!values.is_empty()).then(|| values.to_vec());
+    let mut config = ConfigFile {
+        no_release: list(&args.no_release),
+        include_paths: list(&args.include_path),
+        exclude_paths: list(&args.exclude_path),
+        include_scopes: list(&args.include_scope),
+        exclude_scopes: list(&args.exclude_scope),
+        ignore_root_files: flag(args.ignore_root_files, args.no_ignore_root_files),
+        annotated_only: flag(args.annotated_only, args.no_annotated_only),
+        ..Default::default()
+    };
+    match &args.command {
+        Commands::Require(require) if !require.files.is_empty() => {
+            config.required_files = Some(
+                require
+                    .files
+                    .iter()
+                    .map(|file| file.to_string_lossy().into_owned())
+                    .collect(),
+            );
+        }
+        Commands::Workspace(workspace) => {
+            config.lockstep = flag(workspace.lockstep, workspace.no_lockstep);
+        }
+        _ => {}
+    }
+    config
+}
+
+/// The value of a flag and its `--no-` negation, if either is given.
+fn flag(set: bool, unset: bool) -> Option<bool> {
+    match (set, unset) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Print advisory warnings to stderr; stdout is consumed by CI pipelines.
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("WARNING: {warning}");
+    }
+}
+
+/// Append the `key=value` lines to the output file, or `$GITHUB_OUTPUT`, if
+/// set; otherwise print them.
+fn write_env(report: &str, output_file: Option<PathBuf>) -> Result<(), Error> {
+    let output_file = output_file.or_else(|| std::env::var_os("GITHUB_OUTPUT").map(PathBuf::from));
+    if let Some(path) = output_file {
+        log::debug!("Appending the output to `{}`", path.display());
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(report.as_bytes())?;
+    } else {
+        print!("{report}");
+    }
+
+    Ok(())
+}
+
+/// Override the tag and commit selection where set on the command line.
+fn set_selection(
+    mut calculator_config: CalculatorConfig,
+    prefix: Option<String>,
+    subdir: Option<String>,
+    package: Option<String>,
+) -> CalculatorConfig {
+    if let Some(prefix) = prefix {
+        calculator_config = calculator_config.set_prefix(&prefix);
+    };
+    if subdir.is_some() {
+        calculator_config = calculator_config.set_subdir(subdir.as_deref());
+    };
+    if package.is_some() {
+        calculator_config = calculator_config.set_package(package.as_deref());
+    };
+
+    calculator_config
+}
+
+fn parse_type_level(s: &str) -> Result<(String, Hierarchy), String> {
+    let (commit_type, level) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected TYPE=LEVEL, found `{s}`"))?;
+
+    Ok((commit_type.to_string(), parse_level(level)?))
+}
+
+/// Parse a level that may be `none`, which is skipped as a value of `Hierarchy`
+/// so that it cannot be checked or required.
+fn parse_level(s: &str) -> Result<Hierarchy, String> {
+    if s.eq_ignore_ascii_case("none") {
+        Ok(Hierarchy::None)
+    } else {
+        Hierarchy::from_str(s, true)
+    }
+}
+
+fn parse_re_export(s: &str) -> Result<(String, String), String> {
+    let (package, dependency) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected PACKAGE=DEPENDENCY, found `{s}`"))?;
+
+    Ok((package.to_string(), dependency.to_string()))
+}
+
 fn get_logging(level: log::LevelFilter) -> env_logger::Builder {
     let mut builder = env_logger::Builder::new();
 
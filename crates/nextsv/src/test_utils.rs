@@ -105,6 +105,8 @@ pub(crate) fn gen_conventional_commits() -> ConventionalCommits {
         breaking: false,
         top_type: TopType::Feature,
         major_dep_bumps: vec![],
+        type_levels: HashMap::new(),
+        records: vec![],
     }
 }
 
@@ -133,6 +135,8 @@ pub(crate) fn gen_conventional_commit(
         all_files: files,
         top_type,
         major_dep_bumps: vec![],
+        type_levels: HashMap::new(),
+        records: vec![],
     }
 }
 
@@ -175,5 +179,6 @@ pub(crate) fn gen_current_version(
             pre_release,
             build_meta_data,
         },
+        ..Default::default()
     }
 }
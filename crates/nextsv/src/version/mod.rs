@@ -1,8 +1,10 @@
 mod pre_release;
 mod semantic;
+mod tag_template;
 mod version_tag;
 
 pub(crate) use pre_release::{PreRelease, PreReleaseType};
 pub(crate) use semantic::Semantic;
 pub(crate) use semantic::VersionType;
-pub(crate) use version_tag::VersionTag;
+pub(crate) use tag_template::TagTemplate;
+pub(crate) use version_tag::{TagRejection, VersionTag};
@@ -12,6 +12,8 @@
 use std::cmp::Ordering;
 use std::fmt;
 
+use crate::Error;
+
 use super::PreRelease;
 
 macro_rules! some_or_none_string {
@@ -94,16 +96,23 @@ impl fmt::Display for Semantic {
 
 impl Semantic {
     // Create a new struct specifying each of the semantic version components.
+    //
+    // Returns an error if a component is not a number that fits in a `u32`.
     pub(crate) fn new(
         major: &str,
         minor: &str,
         patch: &str,
         pre_release: &str,
         build_meta_data: &str,
-    ) -> Self {
-        let major: u32 = major.parse().unwrap();
-        let minor: u32 = minor.parse().unwrap();
-        let patch: u32 = patch.parse().unwrap();
+    ) -> Result<Self, Error> {
+        let component = |number: &str| {
+            number
+                .parse::<u32>()
+                .map_err(|_| Error::ComponentOutOfRange(number.to_string()))
+        };
+        let major = component(major)?;
+        let minor = component(minor)?;
+        let patch = component(patch)?;
 
         let pre_release = if pre_release.is_empty() {
             None
@@ -111,13 +120,13 @@ impl Semantic {
             Some(PreRelease::new(pre_release))
         };
 
-        Semantic {
+        Ok(Semantic {
             major,
             minor,
             patch,
             pre_release,
             build_meta_data: some_or_none_string!(build_meta_data),
-        }
+        })
     }
 
     pub(crate) fn increment_pre_release(&mut self) -> &mut Self {
@@ -195,6 +204,21 @@ mod tests {
         assert_eq!("1.0.0", &version.to_string());
     }
 
+    #[rstest]
+    #[case::major("4294967296", "0", "0", "Version component 4294967296 is too large")]
+    #[case::minor("1", "99999999999", "0", "Version component 99999999999 is too large")]
+    #[case::patch("1", "0", "4294967296", "Version component 4294967296 is too large")]
+    fn new_rejects_components_out_of_range(
+        #[case] major: &str,
+        #[case] minor: &str,
+        #[case] patch: &str,
+        #[case] expected: &str,
+    ) {
+        let result = Semantic::new(major, minor, patch, "", "");
+
+        assert_eq!(expected, result.unwrap_err().to_string());
+    }
+
     #[rstest]
     #[case::non_prod(0, 7, 9, "", "", "0.7.9")]
     #[case::first_alpha(1, 0, 0, "alpha.1", "", "1.0.0-alpha.1")]
@@ -302,16 +326,16 @@ mod tests {
     /// SemVer §11: Major version takes highest precedence
     #[rstest]
     #[case::major_gt(
-        Semantic::new("2", "0", "0", "", ""),
-        Semantic::new("1", "0", "0", "", "")
+        Semantic::new("2", "0", "0", "", "").unwrap(),
+        Semantic::new("1", "0", "0", "", "").unwrap()
     )]
     #[case::major_gt_despite_minor(
-        Semantic::new("2", "0", "0", "", ""),
-        Semantic::new("1", "9", "0", "", "")
+        Semantic::new("2", "0", "0", "", "").unwrap(),
+        Semantic::new("1", "9", "0", "", "").unwrap()
     )]
     #[case::major_gt_despite_patch(
-        Semantic::new("2", "0", "0", "", ""),
-        Semantic::new("1", "0", "9", "", "")
+        Semantic::new("2", "0", "0", "", "").unwrap(),
+        Semantic::new("1", "0", "9", "", "").unwrap()
     )]
     fn major_version_precedence(#[case] higher: Semantic, #[case] lower: Semantic) {
         assert!(higher > lower, "{higher} should be > {lower}");
@@ -321,12 +345,12 @@ mod tests {
     /// SemVer §11: Minor version precedence when major is equal
     #[rstest]
     #[case::minor_gt(
-        Semantic::new("1", "1", "0", "", ""),
-        Semantic::new("1", "0", "0", "", "")
+        Semantic::new("1", "1", "0", "", "").unwrap(),
+        Semantic::new("1", "0", "0", "", "").unwrap()
     )]
     #[case::minor_gt_despite_patch(
-        Semantic::new("1", "2", "0", "", ""),
-        Semantic::new("1", "1", "9", "", "")
+        Semantic::new("1", "2", "0", "", "").unwrap(),
+        Semantic::new("1", "1", "9", "", "").unwrap()
     )]
     fn minor_version_precedence(#[case] higher: Semantic, #[case] lower: Semantic) {
         assert!(higher > lower, "{higher} should be > {lower}");
@@ -336,12 +360,12 @@ mod tests {
     /// SemVer §11: Patch version precedence when major and minor are equal
     #[rstest]
     #[case::patch_gt(
-        Semantic::new("1", "0", "1", "", ""),
-        Semantic::new("1", "0", "0", "", "")
+        Semantic::new("1", "0", "1", "", "").unwrap(),
+        Semantic::new("1", "0", "0", "", "").unwrap()
     )]
     #[case::patch_ordering(
-        Semantic::new("0", "1", "3", "", ""),
-        Semantic::new("0", "1", "2", "", "")
+        Semantic::new("0", "1", "3", "", "").unwrap(),
+        Semantic::new("0", "1", "2", "", "").unwrap()
     )]
     fn patch_version_precedence(#[case] higher: Semantic, #[case] lower: Semantic) {
         assert!(higher > lower, "{higher} should be > {lower}");
@@ -351,16 +375,16 @@ mod tests {
     /// SemVer §11: Equal versions
     #[rstest]
     #[case::zeros(
-        Semantic::new("0", "0", "0", "", ""),
-        Semantic::new("0", "0", "0", "", "")
+        Semantic::new("0", "0", "0", "", "").unwrap(),
+        Semantic::new("0", "0", "0", "", "").unwrap()
     )]
     #[case::ones(
-        Semantic::new("1", "1", "1", "", ""),
-        Semantic::new("1", "1", "1", "", "")
+        Semantic::new("1", "1", "1", "", "").unwrap(),
+        Semantic::new("1", "1", "1", "", "").unwrap()
     )]
     #[case::with_pre(
-        Semantic::new("1", "0", "0", "alpha.1", ""),
-        Semantic::new("1", "0", "0", "alpha.1", "")
+        Semantic::new("1", "0", "0", "alpha.1", "").unwrap(),
+        Semantic::new("1", "0", "0", "alpha.1", "").unwrap()
     )]
     fn equal_versions(#[case] a: Semantic, #[case] b: Semantic) {
         assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal, "{a} should equal {b}");
@@ -379,20 +403,20 @@ mod tests {
     /// associated normal version
     #[rstest]
     #[case::alpha_lt_release(
-        Semantic::new("1", "0", "0", "alpha", ""),
-        Semantic::new("1", "0", "0", "", "")
+        Semantic::new("1", "0", "0", "alpha", "").unwrap(),
+        Semantic::new("1", "0", "0", "", "").unwrap()
     )]
     #[case::beta_lt_release(
-        Semantic::new("1", "0", "0", "beta.1", ""),
-        Semantic::new("1", "0", "0", "", "")
+        Semantic::new("1", "0", "0", "beta.1", "").unwrap(),
+        Semantic::new("1", "0", "0", "", "").unwrap()
     )]
     #[case::rc_lt_release(
-        Semantic::new("1", "0", "0", "rc.1", ""),
-        Semantic::new("1", "0", "0", "", "")
+        Semantic::new("1", "0", "0", "rc.1", "").unwrap(),
+        Semantic::new("1", "0", "0", "", "").unwrap()
     )]
     #[case::pre_release_non_prod(
-        Semantic::new("0", "1", "0", "alpha.1", ""),
-        Semantic::new("0", "1", "0", "", "")
+        Semantic::new("0", "1", "0", "alpha.1", "").unwrap(),
+        Semantic::new("0", "1", "0", "", "").unwrap()
     )]
     fn pre_release_lower_than_release(#[case] pre: Semantic, #[case] release: Semantic) {
         assert!(pre < release, "{pre} should be < {release}");
@@ -408,20 +432,20 @@ mod tests {
     /// SemVer §11: Pre-release identifiers compared left to right
     #[rstest]
     #[case::alpha_lt_beta(
-        Semantic::new("1", "0", "0", "alpha", ""),
-        Semantic::new("1", "0", "0", "beta", "")
+        Semantic::new("1", "0", "0", "alpha", "").unwrap(),
+        Semantic::new("1", "0", "0", "beta", "").unwrap()
     )]
     #[case::alpha_1_lt_alpha_2(
-        Semantic::new("1", "0", "0", "alpha.1", ""),
-        Semantic::new("1", "0", "0", "alpha.2", "")
+        Semantic::new("1", "0", "0", "alpha.1", "").unwrap(),
+        Semantic::new("1", "0", "0", "alpha.2", "").unwrap()
     )]
     #[case::beta_2_lt_beta_11(
-        Semantic::new("1", "0", "0", "beta.2", ""),
-        Semantic::new("1", "0", "0", "beta.11", "")
+        Semantic::new("1", "0", "0", "beta.2", "").unwrap(),
+        Semantic::new("1", "0", "0", "beta.11", "").unwrap()
     )]
     #[case::beta_lt_rc(
-        Semantic::new("1", "0", "0", "beta.1", ""),
-        Semantic::new("1", "0", "0", "rc.1", "")
+        Semantic::new("1", "0", "0", "beta.1", "").unwrap(),
+        Semantic::new("1", "0", "0", "rc.1", "").unwrap()
     )]
     fn pre_release_ordering(#[case] lower: Semantic, #[case] higher: Semantic) {
         assert!(lower < higher, "{lower} should be < {higher}");
@@ -435,16 +459,16 @@ mod tests {
     /// SemVer §10: Build metadata does not affect precedence
     #[rstest]
     #[case::different_builds(
-        Semantic::new("1", "0", "0", "", "build.1"),
-        Semantic::new("1", "0", "0", "", "build.2")
+        Semantic::new("1", "0", "0", "", "build.1").unwrap(),
+        Semantic::new("1", "0", "0", "", "build.2").unwrap()
     )]
     #[case::build_vs_no_build(
-        Semantic::new("1", "0", "0", "", "20130313144700"),
-        Semantic::new("1", "0", "0", "", "")
+        Semantic::new("1", "0", "0", "", "20130313144700").unwrap(),
+        Semantic::new("1", "0", "0", "", "").unwrap()
     )]
     #[case::pre_release_different_builds(
-        Semantic::new("1", "0", "0", "alpha.1", "001"),
-        Semantic::new("1", "0", "0", "alpha.1", "exp.sha.5114f85")
+        Semantic::new("1", "0", "0", "alpha.1", "001").unwrap(),
+        Semantic::new("1", "0", "0", "alpha.1", "exp.sha.5114f85").unwrap()
     )]
     fn build_metadata_ignored_for_precedence(#[case] a: Semantic, #[case] b: Semantic) {
         assert_eq!(
@@ -463,14 +487,14 @@ mod tests {
     #[test]
     fn semver_spec_full_precedence_example() {
         let versions = [
-            Semantic::new("1", "0", "0", "alpha", ""),
-            Semantic::new("1", "0", "0", "alpha.1", ""),
-            Semantic::new("1", "0", "0", "alpha.beta", ""),
-            Semantic::new("1", "0", "0", "beta", ""),
-            Semantic::new("1", "0", "0", "beta.2", ""),
-            Semantic::new("1", "0", "0", "beta.11", ""),
-            Semantic::new("1", "0", "0", "rc.1", ""),
-            Semantic::new("1", "0", "0", "", ""),
+            Semantic::new("1", "0", "0", "alpha", "").unwrap(),
+            Semantic::new("1", "0", "0", "alpha.1", "").unwrap(),
+            Semantic::new("1", "0", "0", "alpha.beta", "").unwrap(),
+            Semantic::new("1", "0", "0", "beta", "").unwrap(),
+            Semantic::new("1", "0", "0", "beta.2", "").unwrap(),
+            Semantic::new("1", "0", "0", "beta.11", "").unwrap(),
+            Semantic::new("1", "0", "0", "rc.1", "").unwrap(),
+            Semantic::new("1", "0", "0", "", "").unwrap(),
         ];
 
         // Each version should be less than the next
@@ -488,14 +512,14 @@ mod tests {
     #[test]
     fn sort_produces_semver_order() {
         let mut versions = [
-            Semantic::new("1", "0", "0", "", ""),
-            Semantic::new("1", "0", "0", "beta.11", ""),
-            Semantic::new("1", "0", "0", "alpha", ""),
-            Semantic::new("1", "0", "0", "rc.1", ""),
-            Semantic::new("1", "0", "0", "beta", ""),
-            Semantic::new("1", "0", "0", "alpha.1", ""),
-            Semantic::new("1", "0", "0", "beta.2", ""),
-            Semantic::new("1", "0", "0", "alpha.beta", ""),
+            Semantic::new("1", "0", "0", "", "").unwrap(),
+            Semantic::new("1", "0", "0", "beta.11", "").unwrap(),
+            Semantic::new("1", "0", "0", "alpha", "").unwrap(),
+            Semantic::new("1", "0", "0", "rc.1", "").unwrap(),
+            Semantic::new("1", "0", "0", "beta", "").unwrap(),
+            Semantic::new("1", "0", "0", "alpha.1", "").unwrap(),
+            Semantic::new("1", "0", "0", "beta.2", "").unwrap(),
+            Semantic::new("1", "0", "0", "alpha.beta", "").unwrap(),
         ];
 
         versions.sort();
@@ -519,14 +543,14 @@ mod tests {
     #[test]
     fn sort_mixed_versions() {
         let mut versions = [
-            Semantic::new("2", "0", "0", "", ""),
-            Semantic::new("1", "0", "0", "alpha", ""),
-            Semantic::new("1", "0", "0", "", ""),
-            Semantic::new("0", "1", "0", "", ""),
-            Semantic::new("1", "1", "0", "", ""),
-            Semantic::new("1", "0", "0", "rc.1", ""),
-            Semantic::new("0", "0", "1", "", ""),
-            Semantic::new("1", "0", "1", "", ""),
+            Semantic::new("2", "0", "0", "", "").unwrap(),
+            Semantic::new("1", "0", "0", "alpha", "").unwrap(),
+            Semantic::new("1", "0", "0", "", "").unwrap(),
+            Semantic::new("0", "1", "0", "", "").unwrap(),
+            Semantic::new("1", "1", "0", "", "").unwrap(),
+            Semantic::new("1", "0", "0", "rc.1", "").unwrap(),
+            Semantic::new("0", "0", "1", "", "").unwrap(),
+            Semantic::new("1", "0", "1", "", "").unwrap(),
         ];
 
         versions.sort();
@@ -550,10 +574,10 @@ mod tests {
     #[test]
     fn sort_ignores_build_metadata() {
         let mut versions = [
-            Semantic::new("1", "0", "0", "", "build.2"),
-            Semantic::new("0", "9", "0", "", ""),
-            Semantic::new("1", "0", "0", "", "build.1"),
-            Semantic::new("1", "0", "1", "", ""),
+            Semantic::new("1", "0", "0", "", "build.2").unwrap(),
+            Semantic::new("0", "9", "0", "", "").unwrap(),
+            Semantic::new("1", "0", "0", "", "build.1").unwrap(),
+            Semantic::new("1", "0", "1", "", "").unwrap(),
         ];
 
         versions.sort();
@@ -0,0 +1,273 @@
+//! Templates for the names of version tags
+//!
+
+use std::{
+    fmt,
+    path::{Component, Path},
+};
+
+use regex::Regex;
+
+use crate::Error;
+
+use super::{
+    version_tag::{version_number_valid, SEMANTIC_VERSION},
+    Semantic, VersionTag,
+};
+
+/// The form of the version tags of a package, such as `v{version}`,
+/// `{package}@{version}` or `{path}/v{version}`.
+///
+/// The `{package}` and `{path}` placeholders are replaced by the name and the
+/// directory of the package when the template is created, so a template finds
+/// and formats the tags of a single package.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub(crate) struct TagTemplate {
+    /// The text of the tag before the version number
+    prefix: String,
+    /// The text of the tag after the version number
+    suffix: String,
+}
+
+impl fmt::Display for TagTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{{version}}{}", self.prefix, self.suffix)
+    }
+}
+
+impl TagTemplate {
+    /// The tags named `<prefix><version>`, or `<package>-v<version>` for a
+    /// package of a workspace.
+    pub(crate) fn from_prefix(package: &str, prefix: &str) -> Self {
+        let prefix = if !package.is_empty() {
+            format!("{package}-v")
+        } else {
+            prefix.to_string()
+        };
+
+        TagTemplate {
+            prefix,
+            suffix: String::new(),
+        }
+    }
+
+    /// Expand the placeholders of the template for the package in the
+    /// directory `path`, relative to the root of the repository.
+    ///
+    /// The template must contain `{version}` exactly once.
+    pub(crate) fn new(template: &str, package: &str, path: &str) -> Result<Self, Error> {
+        let path = Path::new(path)
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let expanded = template
+            .replace("{package}", package)
+            .replace("{path}", &path);
+
+        match expanded.split("{version}").collect::<Vec<_>>().as_slice() {
+            [prefix, suffix] => Ok(TagTemplate {
+                prefix: prefix.to_string(),
+                suffix: suffix.to_string(),
+            }),
+            _ => Err(Error::TagTemplate(template.to_string())),
+        }
+    }
+
+    /// The text of the tag before the version number
+    pub(crate) fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The text of the tag after the version number
+    pub(crate) fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// The regex matching the references of the version tags, capturing the
+    /// components of the semantic version.
+    ///
+    /// Tags of the template with a version that is not a semantic version,
+    /// such as `v1.2` or `v1.2.3.4`, are also matched with the version
+    /// captured as `invalid` so that they can be reported.
+    pub(crate) fn regex(&self) -> Result<Regex, Error> {
+        let re_tag = format!(
+            r"^refs/tags/{}(?:{SEMANTIC_VERSION}|(?<invalid>\d.*)){}$",
+            regex::escape(&self.prefix),
+            regex::escape(&self.suffix)
+        );
+        log::debug!("Regex to search for version tags is: `{re_tag}`.");
+
+        Regex::new(&re_tag).map_err(Error::CorruptVersionRegex)
+    }
+
+    /// Parse the reference of a tag, returning `None` if it is not a tag of
+    /// the template and an error if the version of the tag is not valid.
+    pub(crate) fn parse(&self, re: &Regex, refs: &str) -> Result<Option<VersionTag>, Error> {
+        let Some(caps) = re.captures(refs) else {
+            return Ok(None);
+        };
+        if let Some(invalid) = caps.name("invalid") {
+            version_number_valid(invalid.as_str(), "")?;
+            return Err(Error::InvalidVersion(invalid.as_str().to_string()));
+        }
+
+        let semantic_version = Semantic::new(
+            &caps["major"],
+            &caps["minor"],
+            &caps["patch"],
+            caps.name("pre_release").map_or("", |m| m.as_str()),
+            caps.name("build_meta_data").map_or("", |m| m.as_str()),
+        )?;
+
+        let mut version = VersionTag::new(
+            "refs/tags/".to_string(),
+            String::new(),
+            self.prefix.clone(),
+            semantic_version,
+        );
+        version.tag_suffix = self.suffix.clone();
+
+        Ok(Some(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::TagTemplate;
+
+    #[rstest]
+    #[case::prefix_set("", "test-v", "test-v")]
+    #[case::package_set("nextsv", "v", "nextsv-v")]
+    #[case::package_and_prefix_set("nextsv", "test-v", "nextsv-v")]
+    fn test_from_prefix(#[case] package: &str, #[case] prefix: &str, #[case] expected: &str) {
+        let template = TagTemplate::from_prefix(package, prefix);
+
+        assert_eq!(expected, template.prefix());
+        assert_eq!("", template.suffix());
+    }
+
+    #[rstest]
+    #[case::at_version("{package}@{version}", "nextsv@", "")]
+    #[case::go_style("{path}/v{version}", "crates/foo/v", "")]
+    #[case::suffix("release-{version}-{package}", "release-", "-nextsv")]
+    #[case::no_placeholders("v{version}", "v", "")]
+    fn test_new(#[case] template: &str, #[case] prefix: &str, #[case] suffix: &str) {
+        let template = TagTemplate::new(template, "nextsv", "./crates/foo/").unwrap();
+
+        assert_eq!(prefix, template.prefix());
+        assert_eq!(suffix, template.suffix());
+    }
+
+    #[rstest]
+    #[case::no_version("{package}-latest")]
+    #[case::two_versions("{version}-{version}")]
+    fn test_new_invalid(#[case] template: &str) {
+        assert!(TagTemplate::new(template, "nextsv", "").is_err());
+    }
+
+    /// Test that the version tag regex correctly discriminates between
+    /// workspace tags (prefix "v") and crate tags (prefix "gen-changelog-v").
+    ///
+    /// Regression test for <https://github.com/jerus-org/nextsv/issues/443>:
+    /// The unanchored regex `(v\d+\.\d+\.\d+)` incorrectly matches "v0.1.6"
+    /// inside "refs/tags/gen-changelog-v0.1.6".
+    #[rstest]
+    #[case::workspace_prefix_matches_workspace_tag("v", "refs/tags/v0.1.6", true)]
+    #[case::workspace_prefix_must_not_match_crate_tag("v", "refs/tags/gen-changelog-v0.1.6", false)]
+    #[case::crate_prefix_matches_crate_tag(
+        "gen-changelog-v",
+        "refs/tags/gen-changelog-v0.1.6",
+        true
+    )]
+    #[case::crate_prefix_must_not_match_workspace_tag("gen-changelog-v", "refs/tags/v0.1.6", false)]
+    #[case::workspace_prefix_matches_higher_version("v", "refs/tags/v1.2.3", true)]
+    #[case::workspace_prefix_must_not_match_other_crate("v", "refs/tags/nextsv-v2.0.0", false)]
+    #[case::crate_prefix_matches_own_crate("nextsv-v", "refs/tags/nextsv-v2.0.0", true)]
+    #[case::crate_prefix_must_not_match_different_crate(
+        "nextsv-v",
+        "refs/tags/gen-changelog-v0.1.6",
+        false
+    )]
+    #[case::prefix_dot_is_literal("v.", "refs/tags/vx1.2.3", false)]
+    #[case::prefix_dot_matches_dot("v.", "refs/tags/v.1.2.3", true)]
+    #[case::prefix_plus_is_literal("v+", "refs/tags/vv1.2.3", false)]
+    #[case::prefix_plus_matches_plus("v+", "refs/tags/v+1.2.3", true)]
+    fn test_version_tag_regex_anchoring(
+        #[case] version_prefix: &str,
+        #[case] tag: &str,
+        #[case] should_match: bool,
+    ) {
+        let re = TagTemplate::from_prefix("", version_prefix)
+            .regex()
+            .unwrap();
+        let matched = re.is_match(tag);
+        assert_eq!(
+            should_match, matched,
+            "prefix={version_prefix:?} tag={tag:?}: expected match={should_match} got={matched}"
+        );
+    }
+
+    #[rstest]
+    #[case::at_version("{package}@{version}", "refs/tags/nextsv@1.2.3", Some("nextsv@1.2.3"))]
+    #[case::go_style(
+        "{path}/v{version}",
+        "refs/tags/crates/foo/v1.2.3-rc.1",
+        Some("crates/foo/v1.2.3-rc.1")
+    )]
+    #[case::suffix(
+        "release-{version}-{package}",
+        "refs/tags/release-1.2.3-nextsv",
+        Some("release-1.2.3-nextsv")
+    )]
+    #[case::suffix_after_pre_release(
+        "release-{version}-{package}",
+        "refs/tags/release-1.2.3-beta.2-nextsv",
+        Some("release-1.2.3-beta.2-nextsv")
+    )]
+    #[case::other_package("{package}@{version}", "refs/tags/other@1.2.3", None)]
+    #[case::missing_suffix("release-{version}-{package}", "refs/tags/release-1.2.3", None)]
+    #[case::not_a_version("{package}@{version}", "refs/tags/nextsv@latest", None)]
+    #[case::other_prefix("v{version}", "refs/tags/vnext", None)]
+    fn test_parse(#[case] template: &str, #[case] refs: &str, #[case] expected: Option<&str>) {
+        let template = TagTemplate::new(template, "nextsv", "crates/foo").unwrap();
+        let re = template.regex().unwrap();
+
+        let version = template.parse(&re, refs).unwrap();
+        assert_eq!(
+            expected.map(String::from),
+            version.as_ref().map(|version| version.tag_name())
+        );
+        if let Some(version) = version {
+            assert_eq!(refs, version.to_string());
+        }
+    }
+
+    #[rstest]
+    #[case::too_few_components(
+        "refs/tags/v1.2",
+        "Version must have three components but only 2 found"
+    )]
+    #[case::too_many_components(
+        "refs/tags/v1.2.3.4",
+        "Version must have three components but at least 4 were found"
+    )]
+    #[case::not_a_number("refs/tags/v1.x.0", "Version must be a number but found x")]
+    #[case::component_too_large(
+        "refs/tags/v99999999999.0.0",
+        "Version component 99999999999 is too large"
+    )]
+    #[case::leading_zero("refs/tags/v01.2.3", "`01.2.3` is not a valid semantic version")]
+    fn test_parse_invalid_version(#[case] refs: &str, #[case] expected: &str) {
+        let template = TagTemplate::from_prefix("", "v");
+        let re = template.regex().unwrap();
+
+        let result = template.parse(&re, refs);
+        assert_eq!(expected, result.unwrap_err().to_string());
+    }
+}
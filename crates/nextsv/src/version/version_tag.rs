@@ -1,11 +1,14 @@
 use std::{cmp::Ordering, fmt};
 
-use git2::Repository;
+use git2::{Oid, Repository, Time};
 use regex::Regex;
 
-use crate::Error;
+use crate::{Error, TagSelection};
 
-use super::Semantic;
+use super::{Semantic, TagTemplate};
+
+/// The regex pattern for a semantic version capturing its components
+pub(super) const SEMANTIC_VERSION: &str = r"(?<major>0|[1-9]\d*)\.(?<minor>0|[1-9]\d*)\.(?<patch>0|[1-9]\d*)(?:-(?<pre_release>(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+(?<build_meta_data>[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?";
 
 /// The VersionTag data structure represents a git tag containing a
 /// semantic version number.
@@ -16,6 +19,38 @@ pub(crate) struct VersionTag {
     pub(crate) tag_prefix: String,
     pub(crate) version_prefix: String,
     pub(crate) semantic_version: Semantic,
+    /// Any text after the semantic version, set by a tag template
+    pub(crate) tag_suffix: String,
+    /// The tagger and message if the tag is an annotated tag
+    pub(crate) annotation: Option<Box<TagAnnotation>>,
+    /// The date of the tag, or of the tagged commit for a lightweight tag
+    pub(crate) date: Option<Time>,
+}
+
+/// Why a tag of the repository is not used as a version tag
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum TagRejection {
+    /// The name of the tag does not match the tag template
+    NoMatch,
+    /// The tag matches the template but the version is not valid
+    InvalidVersion(String),
+    /// The tag is a lightweight tag and only annotated tags are considered
+    Lightweight,
+    /// The tag is not chosen by the tag selection from the start commit
+    NotSelected(TagSelection),
+}
+
+/// A tag of the repository, by name, and the version tag found in it or the
+/// reason it was rejected
+pub(crate) type CheckedTag = (String, Result<VersionTag, TagRejection>);
+
+/// The details recorded in an annotated tag
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TagAnnotation {
+    /// The name and email of the tagger
+    pub(crate) tagger: Option<String>,
+    /// The tag message
+    pub(crate) message: String,
 }
 
 impl PartialEq for VersionTag {
@@ -42,15 +77,15 @@ impl fmt::Display for VersionTag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}{}{}{}",
-            self.refs, self.tag_prefix, self.version_prefix, self.semantic_version
+            "{}{}{}{}{}",
+            self.refs, self.tag_prefix, self.version_prefix, self.semantic_version, self.tag_suffix
         )
     }
 }
 
 impl VersionTag {
     // Create a new struct specifying each of the semantic version components.
-    fn new(
+    pub(super) fn new(
         refs: String,
         tag_prefix: String,
         version_prefix: String,
@@ -61,68 +96,32 @@ impl VersionTag {
             tag_prefix,
             version_prefix,
             semantic_version,
+            tag_suffix: String::new(),
+            annotation: None,
+            date: None,
         }
     }
-    /// # Parse a tag and return a struct
-    /// String format expect: <version_prefix>x.y.z<-pre_release><+build_data>
-    ///
-    /// ## Fields
-    ///
-    /// tag - the tag proposed as a semantic version tag
-    /// version_prefix - any string before the semantic version number
-    ///
-    /// # Example
-    ///
-    /// Parse a tag into a semantic version number where "v" is used to identify
-    /// tags representing semantic version numbers.
-    ///
-    /// ```no_comp
-    /// # fn main() -> Result<(), nextsv::Error> {
-    /// use self::VersionTag;
-    ///
-    /// let tag = "refs/tags/v0.2.3";
-    /// let semantic_version = VersionTag::parse(tag, "v")?;
-    ///
-    /// assert_eq!(0, semantic_version.version().major());
-    /// assert_eq!(2, semantic_version.version().minor());
-    /// assert_eq!(3, semantic_version.version().patch());
-    ///
-    /// # Ok(())
-    /// # }
-    /// ```
-    /// to identify tags with semantic version numbers
-    /// the tag name can be parsed
-    pub(crate) fn parse(tag: &str, version_prefix: &str) -> Result<Self, Error> {
-        let re_tag = format!(
-            r"(?<refs>(refs/tags/)*)(?<tag_prefix>.*)(?<version_prefix>{version_prefix})(?<major>0|[1-9]\d*)\.(?<minor>0|[1-9]\d*)\.(?<patch>0|[1-9]\d*)(?:-(?<pre_release>(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+(?<build_meta_data>[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?$"
-        );
-
-        let re = Regex::new(&re_tag).unwrap();
-
-        log::trace!("Parsing git tag `{tag}` into VersionTag");
-        let caps_res = re.captures(tag);
-        log::trace!("Regex captures result: {caps_res:?}");
-        let Some(caps) = caps_res else {
-            version_number_valid(tag, version_prefix)?;
-            panic!("Tag validation failed");
-        };
-
-        let semantic_version = Semantic::new(
-            caps.name("major").unwrap().as_str(),
-            caps.name("minor").unwrap().as_str(),
-            caps.name("patch").unwrap().as_str(),
-            caps.name("pre_release").map_or("", |m| m.as_str()),
-            caps.name("build_meta_data").map_or("", |m| m.as_str()),
+    /// Create the version tag for the first version of the package, for use
+    /// when no version tag is found in the repository.
+    pub(crate) fn first(version: &str, template: &TagTemplate) -> Result<Self, Error> {
+        version_number_valid(version, "")?;
+
+        let refs = format!(
+            "refs/tags/{}{version}{}",
+            template.prefix(),
+            template.suffix()
         );
+        template
+            .parse(&template.regex()?, &refs)?
+            .ok_or_else(|| Error::InvalidVersion(version.to_string()))
+    }
 
-        Ok(VersionTag::new(
-            caps.name("refs").map_or("", |m| m.as_str()).to_string(),
-            caps.name("tag_prefix")
-                .map_or("", |m| m.as_str())
-                .to_string(),
-            version_prefix.to_string(),
-            semantic_version,
-        ))
+    /// The name of the tag, without the `refs/tags/` prefix
+    pub(crate) fn tag_name(&self) -> String {
+        format!(
+            "{}{}{}{}",
+            self.tag_prefix, self.version_prefix, self.semantic_version, self.tag_suffix
+        )
     }
 
     /// Provide a mutable reference to the semantic version
@@ -130,86 +129,291 @@ impl VersionTag {
         &mut self.semantic_version
     }
 
+    /// True if the tag is an annotated tag
+    pub(crate) fn is_annotated(&self) -> bool {
+        self.annotation.is_some()
+    }
+
+    /// The date of the tag formatted as `YYYY-MM-DD HH:MM:SS +HHMM`
+    pub(crate) fn date_string(&self) -> Option<String> {
+        self.date.as_ref().map(format_time)
+    }
+
+    /// Add the annotation and date of the tag pointing at `id`.
+    fn with_details(mut self, repo: &Repository, id: Oid) -> Self {
+        match repo.find_tag(id) {
+            Ok(tag) => {
+                let tagger = tag.tagger();
+                self.date = match &tagger {
+                    Some(tagger) => Some(tagger.when()),
+                    None => tag.peel().ok().and_then(|object| {
+                        object.peel_to_commit().ok().map(|commit| commit.time())
+                    }),
+                };
+                self.annotation = Some(Box::new(TagAnnotation {
+                    tagger: tagger.map(|tagger| tagger.to_string()),
+                    message: String::from_utf8_lossy(tag.message_bytes().unwrap_or_default())
+                        .trim_end()
+                        .to_string(),
+                }));
+            }
+            Err(_) => {
+                log::trace!("Tag `{self}` is a lightweight tag");
+                self.date = repo.find_commit(id).ok().map(|commit| commit.time());
+            }
+        }
+        self
+    }
+
     /// Find the latest version tag in a repo
     ///
+    /// The tags considered are chosen by the selection; for the reachable
+    /// selections the ancestors of the `start` revision, or HEAD, are searched.
+    /// If `annotated_only` is set lightweight tags are ignored.
     pub(crate) fn find_in_repo(
         repo: &Repository,
-        package: &str,
-        version_prefix: &str,
+        template: &TagTemplate,
+        selection: TagSelection,
+        start: Option<&str>,
+        annotated_only: bool,
     ) -> Result<Self, Error> {
         log::debug!("Repository opened to find latest version tag.");
 
-        let version_prefix = get_tag_prefix(package, version_prefix);
+        let versions = VersionTag::check_tags(repo, template, selection, start, annotated_only)?
+            .into_iter()
+            .filter_map(|(_, outcome)| outcome.ok())
+            .collect();
+
+        VersionTag::select_current(versions, selection, template).ok_or(Error::NoVersionTag)
+    }
 
+    /// Check every tag in the repo as a version tag of the template.
+    ///
+    /// Tags that match the template but do not hold a valid version are
+    /// logged and rejected rather than failing the calculation. The tags are
+    /// returned in the order of their names.
+    pub(crate) fn check_tags(
+        repo: &Repository,
+        template: &TagTemplate,
+        selection: TagSelection,
+        start: Option<&str>,
+        annotated_only: bool,
+    ) -> Result<Vec<CheckedTag>, Error> {
         // Setup regex to test the tag for a version number: major.minor,patch
-        let re_version = build_version_regex(&version_prefix);
-        log::debug!("Regex to search for version tags is: `{re_version}`.");
-        let re = match Regex::new(&re_version) {
-            Ok(r) => r,
-            Err(e) => return Err(Error::CorruptVersionRegex(e)),
-        };
+        let re = template.regex()?;
 
-        let mut versions = vec![];
-        repo.tag_foreach(|_id, tag| {
+        let mut tags = vec![];
+        repo.tag_foreach(|id, tag| {
             if let Ok(tag) = String::from_utf8(tag.to_owned()) {
-                log::trace!("Is git tag `{tag}` a version tag?");
-                if let Some(version) = re.captures(&tag) {
-                    log::trace!("Captured version: {version:?}");
-                    let version = VersionTag::parse(&tag, &version_prefix).unwrap();
-                    versions.push(version);
-                }
+                tags.push((id, tag));
             }
             true
         })?;
+        tags.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        let mut checked = vec![];
+        for (id, tag) in tags {
+            log::trace!("Is git tag `{tag}` a version tag?");
+            let name = tag.trim_start_matches("refs/tags/").to_string();
+            let outcome = match template.parse(&re, &tag) {
+                Ok(Some(version)) => {
+                    log::trace!("Captured version: {version:?}");
+                    let version = version.with_details(repo, id);
+                    if annotated_only && !version.is_annotated() {
+                        log::debug!("Skipping lightweight tag `{name}`");
+                        Err(TagRejection::Lightweight)
+                    } else {
+                        Ok(version)
+                    }
+                }
+                Ok(None) => Err(TagRejection::NoMatch),
+                Err(e) => {
+                    log::warn!("Skipping tag `{name}` as the version is not valid: {e}");
+                    Err(TagRejection::InvalidVersion(e.to_string()))
+                }
+            };
+            checked.push((name, outcome));
+        }
+
+        if selection != TagSelection::Any {
+            let versions = checked
+                .iter()
+                .filter_map(|(_, outcome)| outcome.as_ref().ok().cloned())
+                .collect();
+            let selected: Vec<String> = select_reachable(repo, versions, selection, start)?
+                .iter()
+                .map(|version| version.to_string())
+                .collect();
+            for (_, outcome) in &mut checked {
+                if outcome
+                    .as_ref()
+                    .is_ok_and(|version| !selected.contains(&version.to_string()))
+                {
+                    *outcome = Err(TagRejection::NotSelected(selection));
+                }
+            }
+        }
 
-        trace_items(versions.clone(), &version_prefix);
+        Ok(checked)
+    }
+
+    /// Choose the current version from the version tags by the selection.
+    pub(crate) fn select_current(
+        mut versions: Vec<VersionTag>,
+        selection: TagSelection,
+        template: &TagTemplate,
+    ) -> Option<Self> {
+        trace_items(versions.clone(), template);
         log::trace!("Original last version: {:?}", versions.last());
-        versions.sort();
+        if selection == TagSelection::Latest {
+            versions.sort_by(|a, b| {
+                let seconds = |version: &VersionTag| version.date.map(|date| date.seconds());
+                seconds(a).cmp(&seconds(b)).then_with(|| a.cmp(b))
+            });
+        } else {
+            versions.sort();
+        }
         log::debug!("Version tags have been sorted");
-        trace_items(versions.clone(), &version_prefix);
+        trace_items(versions.clone(), template);
 
-        let current_version = match versions.last().cloned() {
-            Some(v) => {
-                log::trace!("latest version found is {v:?}");
-                v
-            }
-            None => return Err(Error::NoVersionTag),
+        let current_version = versions.pop();
+        log::trace!("latest version found is {current_version:?}");
+        current_version
+    }
+
+    /// Find the version tag named by the revision.
+    ///
+    /// Returns `None` if the revision is not the name of a version tag of the
+    /// template, for example when it is a branch or a commit id.
+    pub(crate) fn from_revision(
+        repo: &Repository,
+        revision: &str,
+        template: &TagTemplate,
+    ) -> Option<Self> {
+        let refs = format!("refs/tags/{}", revision.trim_start_matches("refs/tags/"));
+
+        let re = template.regex().ok()?;
+        let (Ok(Some(version)), Ok(id)) = (template.parse(&re, &refs), repo.refname_to_id(&refs))
+        else {
+            log::debug!("Revision `{revision}` is not a version tag");
+            return None;
         };
-        Ok(current_version)
+
+        Some(version.with_details(repo, id))
     }
 }
 
-/// Build the regex pattern for matching version tags.
+/// Keep the version tags reachable from the `start` revision, or HEAD.
 ///
-/// Anchors after `refs/tags/` so that prefix "v" only matches workspace
-/// tags like `refs/tags/v1.0.0` and not crate tags like
-/// `refs/tags/gen-changelog-v1.0.0`.
-fn build_version_regex(version_prefix: &str) -> String {
-    format!(r"^refs/tags/{version_prefix}\d+\.\d+\.\d+")
-}
+/// For the nearest selection only the tags on the first tagged commit found
+/// walking back from the start are kept.
+fn select_reachable(
+    repo: &Repository,
+    versions: Vec<VersionTag>,
+    selection: TagSelection,
+    start: Option<&str>,
+) -> Result<Vec<VersionTag>, Error> {
+    let start = match start {
+        Some(revision) => repo.revparse_single(revision)?.peel_to_commit()?.id(),
+        None => repo.head()?.peel_to_commit()?.id(),
+    };
+    log::debug!("Selecting the {selection} version tag reachable from `{start}`");
+
+    let mut tagged: Vec<(Oid, VersionTag)> = vec![];
+    for version in versions {
+        match repo
+            .revparse_single(&version.to_string())
+            .and_then(|object| object.peel_to_commit())
+        {
+            Ok(commit) => tagged.push((commit.id(), version)),
+            Err(e) => {
+                log::warn!("Skipping version tag `{version}` as it does not tag a commit: {e}")
+            }
+        }
+    }
 
-fn get_tag_prefix(package: &str, prefix: &str) -> String {
-    if !package.is_empty() {
-        format!("{package}-v")
-    } else {
-        prefix.to_string()
+    if selection == TagSelection::Nearest {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        revwalk.push(start)?;
+        for id in revwalk {
+            let id = id?;
+            let nearest: Vec<_> = tagged
+                .iter()
+                .filter(|(tag_id, _)| *tag_id == id)
+                .map(|(_, version)| version.clone())
+                .collect();
+            if !nearest.is_empty() {
+                return Ok(nearest);
+            }
+        }
+        return Ok(vec![]);
+    }
+
+    let mut reachable = vec![];
+    for (id, version) in tagged {
+        if id == start || repo.graph_descendant_of(start, id)? {
+            reachable.push(version);
+        } else {
+            log::debug!("Version tag `{version}` is not reachable");
+        }
     }
+
+    Ok(reachable)
 }
 
-fn trace_items(versions: Vec<VersionTag>, prefix: &str) {
-    log::trace!("Tags with semantic version numbers prefixed with `{prefix}`");
+fn trace_items(versions: Vec<VersionTag>, template: &TagTemplate) {
+    log::trace!(
+        "Tags with semantic version numbers prefixed with `{}` and suffixed with `{}`",
+        template.prefix(),
+        template.suffix()
+    );
     for ver in &versions {
         log::trace!("\t{ver}");
     }
 }
 
-fn version_number_valid(tag: &str, version_prefix: &str) -> Result<(), Error> {
+/// Format the time as `YYYY-MM-DD HH:MM:SS +HHMM` in the time zone in which
+/// it was recorded.
+pub(crate) fn format_time(time: &Time) -> String {
+    let offset = i64::from(time.offset_minutes());
+    let local = time.seconds() + offset * 60;
+    let (days, seconds) = (local.div_euclid(86_400), local.rem_euclid(86_400));
+
+    // Convert the days since 1970-01-01 to the civil date
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} {}{:02}{:02}",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+        time.sign(),
+        offset.abs() / 60,
+        offset.abs() % 60,
+    )
+}
+
+pub(super) fn version_number_valid(tag: &str, version_prefix: &str) -> Result<(), Error> {
     log::debug!("Validating the tag `{tag}` with version identified by `{version_prefix}`");
     let tag = tag.trim_start_matches("refs/tags/");
     log::debug!("The tag after git prefix is stripped is `{tag}`");
 
     let mut version = if !version_prefix.is_empty() {
-        let re = Regex::new(version_prefix).unwrap();
+        let re = Regex::new(&regex::escape(version_prefix)).unwrap();
         let m_res = re.find(tag);
 
         // the tag string must start with the version_prefix
@@ -293,6 +497,11 @@ mod tests {
         gen_current_version("v", 1, 17, 3, Some(pre_release), Some("2000".to_string()))
     }
 
+    fn parse_tag(refs: &str, prefix: &str) -> Result<Option<VersionTag>, Error> {
+        let template = TagTemplate::from_prefix("", prefix);
+        template.parse(&template.regex()?, refs)
+    }
+
     #[test]
     fn test_partial_eq() {
         let tag1 = version_tag_example_one();
@@ -383,15 +592,14 @@ mod tests {
     #[case::minor_update_first_version("refs/tags/v1.1.0", "v", true)]
     #[case::custom_pre_release("refs/tags/v2.0.0-pre.1+circle.1", "v", true)]
     #[case::alphanumeric_build("refs/tags/v2.0.0-pre.2+circle.14", "v", true)]
-    #[case::no_refs("v2.0.0-pre.2+circle.14", "v", true)]
+    #[case::no_refs("v2.0.0-pre.2+circle.14", "v", false)]
     fn test_parse_value(#[case] input: &str, #[case] version_prefix: &str, #[case] expected: bool) {
         get_test_logger();
 
-        let result = VersionTag::parse(input, version_prefix);
+        let result = parse_tag(input, version_prefix);
         log::debug!("the result is:{result:?}");
-        assert_eq!(expected, result.is_ok());
-        if result.is_ok() {
-            let version = result.unwrap();
+        assert_eq!(expected, matches!(result, Ok(Some(_))));
+        if let Ok(Some(version)) = result {
             assert_eq!(input, version.to_string().as_str());
         }
     }
@@ -443,71 +651,28 @@ mod tests {
         assert_eq!(expected_result, format!("{result:?}"));
         assert_eq!(expected_pass, result.is_ok());
     }
-    #[rstest]
-    #[case::prefix_set("", "test-v", "test-v")]
-    #[case::package_set("nextsv", "v", "nextsv-v")]
-    #[case::package_and_prefix_set("nextsv", "test-v", "nextsv-v")]
 
-    fn test_get_package_prefix(
-        #[case] package: &str,
-        #[case] prefix: &str,
-        #[case] expected_result: &str,
-    ) {
-        get_test_logger();
-
-        let version_prefix = get_tag_prefix(package, prefix);
-        println!("result: {version_prefix:?}");
-        assert_eq!(expected_result.to_string(), version_prefix);
-    }
-
-    /// Test that the version tag regex correctly discriminates between
-    /// workspace tags (prefix "v") and crate tags (prefix "gen-changelog-v").
-    ///
-    /// Regression test for <https://github.com/jerus-org/nextsv/issues/443>:
-    /// The unanchored regex `(v\d+\.\d+\.\d+)` incorrectly matches "v0.1.6"
-    /// inside "refs/tags/gen-changelog-v0.1.6".
     #[rstest]
-    #[case::workspace_prefix_matches_workspace_tag("v", "refs/tags/v0.1.6", true)]
-    #[case::workspace_prefix_must_not_match_crate_tag("v", "refs/tags/gen-changelog-v0.1.6", false)]
-    #[case::crate_prefix_matches_crate_tag(
-        "gen-changelog-v",
-        "refs/tags/gen-changelog-v0.1.6",
-        true
-    )]
-    #[case::crate_prefix_must_not_match_workspace_tag("gen-changelog-v", "refs/tags/v0.1.6", false)]
-    #[case::workspace_prefix_matches_higher_version("v", "refs/tags/v1.2.3", true)]
-    #[case::workspace_prefix_must_not_match_other_crate("v", "refs/tags/nextsv-v2.0.0", false)]
-    #[case::crate_prefix_matches_own_crate("nextsv-v", "refs/tags/nextsv-v2.0.0", true)]
-    #[case::crate_prefix_must_not_match_different_crate(
-        "nextsv-v",
-        "refs/tags/gen-changelog-v0.1.6",
-        false
-    )]
-    fn test_version_tag_regex_anchoring(
-        #[case] version_prefix: &str,
-        #[case] tag: &str,
-        #[case] should_match: bool,
-    ) {
+    #[case::too_few_components("refs/tags/v1.2", "v")]
+    #[case::leading_zero("refs/tags/v01.2.3", "v")]
+    #[case::component_too_large("refs/tags/v4294967296.0.0", "v")]
+    fn test_parse_invalid_tag_is_error(#[case] tag: &str, #[case] version_prefix: &str) {
         get_test_logger();
 
-        let re_version = build_version_regex(version_prefix);
-        let re = Regex::new(&re_version).unwrap();
-        let matched = re.is_match(tag);
-        assert_eq!(
-            should_match, matched,
-            "prefix={version_prefix:?} tag={tag:?}: expected match={should_match} got={matched}"
-        );
+        let result = parse_tag(tag, version_prefix);
+        println!("result: {result:?}");
+        assert!(result.is_err());
     }
 
     #[test]
     fn tag_broken_down_correctly() {
         let tag = "refs/tags/hcaptcha-v2.3.1-Beta.3+20876.675";
 
-        let test_version = VersionTag::parse(tag, "v").unwrap();
+        let test_version = parse_tag(tag, "hcaptcha-v").unwrap().unwrap();
 
         assert_eq!("refs/tags/", test_version.refs);
-        assert_eq!("hcaptcha-", test_version.tag_prefix);
-        assert_eq!("v", test_version.version_prefix);
+        assert_eq!("", test_version.tag_prefix);
+        assert_eq!("hcaptcha-v", test_version.version_prefix);
         assert_eq!(
             "2.3.1-Beta.3+20876.675",
             test_version.semantic_version.to_string().as_str()
@@ -533,4 +698,36 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[rstest]
+    #[case::prefix("0.1.0", "", "v", Some("v0.1.0"))]
+    #[case::package("0.3.0", "nextsv", "v", Some("nextsv-v0.3.0"))]
+    #[case::pre_release("1.0.0-alpha.1", "", "v", Some("v1.0.0-alpha.1"))]
+    #[case::too_few_components("1.0", "", "v", None)]
+    #[case::not_a_number("1.x.0", "", "v", None)]
+    fn test_first(
+        #[case] version: &str,
+        #[case] package: &str,
+        #[case] prefix: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        get_test_logger();
+
+        let result = VersionTag::first(version, &TagTemplate::from_prefix(package, prefix));
+        println!("result: {result:?}");
+        assert_eq!(
+            expected.map(String::from),
+            result.ok().map(|version| version.tag_name())
+        );
+    }
+
+    #[rstest]
+    #[case::epoch(0, 0, "1970-01-01 00:00:00 +0000")]
+    #[case::east_of_utc(1_700_000_000, 60, "2023-11-14 23:13:20 +0100")]
+    #[case::west_of_utc(1_700_000_000, -330, "2023-11-14 16:43:20 -0530")]
+    #[case::leap_day(951_782_400, 0, "2000-02-29 00:00:00 +0000")]
+    #[case::before_epoch(-86_400, 0, "1969-12-31 00:00:00 +0000")]
+    fn test_format_time(#[case] seconds: i64, #[case] offset: i32, #[case] expected: &str) {
+        assert_eq!(expected, format_time(&Time::new(seconds, offset)));
+    }
 }
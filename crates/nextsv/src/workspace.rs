@@ -1,4 +1,7 @@
-use std::path::Path;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::Error;
 use cargo_toml::Manifest;
@@ -11,6 +14,8 @@ use cargo_toml::Manifest;
 pub struct Workspace {
     /// The Cargo.toml of the workspace
     pub manifest: Manifest,
+    /// The directory containing the Cargo.toml of the workspace
+    pub root: PathBuf,
 }
 
 impl Workspace {
@@ -29,11 +34,54 @@ impl Workspace {
     /// ```
     pub fn new(ws_cargo_toml: &Path) -> Result<Self, Error> {
         let manifest = Manifest::from_path(ws_cargo_toml)?;
+        let root = match ws_cargo_toml.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
 
-        Ok(Self { manifest })
+        Ok(Self { manifest, root })
+    }
+
+    /// Find the workspace containing the directory
+    ///
+    /// Searches the directory and its parents, up to the root of the git
+    /// repository, for a Cargo.toml with a `[workspace]` table, so the
+    /// workspace is found when running from the directory of a member package.
+    ///
+    /// # Example
+    ///     
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # fn main() -> Result<(),nextsv::Error> {
+    /// # use nextsv::Workspace;
+    ///     let workspace = Workspace::discover(Path::new("."))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn discover(dir: &Path) -> Result<Self, Error> {
+        let dir = dir.canonicalize()?;
+        for ancestor in dir.ancestors() {
+            let cargo_toml = ancestor.join("Cargo.toml");
+            if cargo_toml.is_file() {
+                let manifest = Manifest::from_slice(&fs::read(&cargo_toml)?)?;
+                if manifest.workspace.is_some() {
+                    log::debug!("Found the workspace at `{}`", ancestor.display());
+                    return Workspace::new(&cargo_toml);
+                }
+            }
+            if ancestor.join(".git").exists() {
+                break;
+            }
+        }
+
+        Err(Error::NotWorkspace)
     }
 
     /// Returns the list of packages in the workspace
+    ///
+    /// Glob patterns in `members` are expanded and paths listed in `exclude`
+    /// are removed. Fields inherited from the workspace, such as
+    /// `version.workspace = true`, are resolved.
     ///     
     /// # Example
     ///     
@@ -43,39 +91,89 @@ impl Workspace {
     /// # use nextsv::Workspace;
     ///     let path = Path::new("./Cargo.toml");
     ///     let workspace = Workspace::new(path)?;
-    ///     let packages = workspace.packages();
+    ///     let packages = workspace.packages()?;
     /// # Ok(())
     /// # }
     /// ```
     ///
-    pub fn packages(&self) -> Option<Vec<Package>> {
-        if let Some(workspace) = &self.manifest.workspace {
-            let members = &workspace.members;
-
-            let mut packages = Vec::new();
-
-            for member in members {
-                let member_file = format!("./{member}/Cargo.toml");
-                let path = Path::new(&member_file);
-                let manifest = Manifest::from_path(path).unwrap();
-                if let Some(package) = manifest.package {
-                    let name = package.name;
-                    let version = package.version.get().unwrap().to_string();
-
-                    let package = Package {
-                        name,
-                        version,
-                        member: member.to_string(),
-                    };
-
-                    packages.push(package);
+    pub fn packages(&self) -> Result<Vec<Package>, Error> {
+        let mut packages = Vec::new();
+
+        for member in self.members()? {
+            let member_file = self.root.join(&member).join("Cargo.toml");
+            log::debug!("Reading workspace member `{}`", member_file.display());
+            let mut manifest = Manifest::from_slice(&fs::read(&member_file)?)?;
+            manifest.complete_from_path_and_workspace(
+                &member_file,
+                Some((&self.manifest, self.root.as_path())),
+            )?;
+            let Some(package) = manifest.package else {
+                log::warn!("Workspace member `{member}` is not a package");
+                continue;
+            };
+
+            let dependencies = manifest
+                .dependencies
+                .iter()
+                .chain(manifest.build_dependencies.iter())
+                .map(|(name, dependency)| dependency.package().unwrap_or(name).to_string())
+                .collect();
+
+            packages.push(Package {
+                name: package.name,
+                version: package.version.get()?.to_string(),
+                member,
+                dependencies,
+            });
+        }
+
+        Ok(packages)
+    }
+
+    /// Expand the member patterns to the directories of the members,
+    /// relative to the workspace root.
+    ///
+    /// The root is a member, as `.`, when its manifest also has a `[package]`.
+    pub(crate) fn members(&self) -> Result<Vec<String>, Error> {
+        let Some(workspace) = &self.manifest.workspace else {
+            return Err(Error::NotWorkspace);
+        };
+
+        let mut members = Vec::new();
+        if self.manifest.package.is_some() {
+            members.push(String::from("."));
+        }
+        for pattern in &workspace.members {
+            let full_pattern = self.root.join(pattern);
+            let mut matched = false;
+            for path in glob::glob(&full_pattern.to_string_lossy())?.flatten() {
+                matched = true;
+                if !path.join("Cargo.toml").is_file() {
+                    log::debug!("Skipping `{}` as it has no Cargo.toml", path.display());
+                    continue;
+                }
+                let member = relative_member(&path, &self.root);
+                if is_excluded(&member, &workspace.exclude) {
+                    log::debug!("Excluding workspace member `{member}`");
+                    continue;
+                }
+                if !members.contains(&member) {
+                    members.push(member);
                 }
             }
-
-            return Some(packages);
+            if !matched && !is_glob(pattern) {
+                // A literal member that does not exist is reported when it is read.
+                members.push(pattern.trim_end_matches('/').to_string());
+            }
         }
 
-        None
+        Ok(members)
+    }
+
+    /// The path of the member directory relative to `dir`, such as the
+    /// working directory of the repository.
+    pub(crate) fn member_path(&self, member: &str, dir: &Path) -> String {
+        relative_member(&self.root.join(member), dir)
     }
 }
 
@@ -84,4 +182,189 @@ pub struct Package {
     pub name: String,
     pub version: String,
     pub member: String,
+    pub dependencies: Vec<String>,
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// The path of the member relative to the workspace root using `/` separators.
+fn relative_member(path: &Path, root: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let relative = relative.strip_prefix(".").unwrap_or(relative);
+
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// True if the member is, or is inside, one of the excluded paths.
+fn is_excluded(member: &str, exclude: &[String]) -> bool {
+    exclude.iter().any(|excluded| {
+        let excluded = excluded.trim_start_matches("./").trim_end_matches('/');
+        member == excluded || member.starts_with(&format!("{excluded}/"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use rstest::rstest;
+
+    use super::{is_excluded, relative_member, Workspace};
+    use crate::Error;
+
+    /// Create a workspace with glob members, an excluded member and
+    /// versions inherited from the workspace.
+    fn create_test_workspace(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("nextsv-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n\n[workspace.package]\nversion = \"0.3.0\"\n",
+        )
+        .unwrap();
+        for (package, version) in [
+            ("one", "version.workspace = true"),
+            ("two", "version = \"1.2.0\""),
+            ("old", "version = \"0.1.0\""),
+        ] {
+            let member = root.join("crates").join(package);
+            fs::create_dir_all(&member).unwrap();
+            fs::write(
+                member.join("Cargo.toml"),
+                format!("[package]\nname = \"{package}\"\n{version}\n\n[dependencies]\none = {{ path = \"../one\" }}\n"),
+            )
+            .unwrap();
+        }
+        fs::create_dir_all(root.join("crates").join("docs")).unwrap();
+
+        root
+    }
+
+    #[test]
+    fn test_packages_resolves_globs_excludes_and_inheritance() {
+        let root = create_test_workspace("packages");
+
+        let packages = Workspace::new(&root.join("Cargo.toml"))
+            .unwrap()
+            .packages()
+            .unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        let found: Vec<_> = packages
+            .iter()
+            .map(|package| {
+                (
+                    package.name.as_str(),
+                    package.version.as_str(),
+                    package.member.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("one", "0.3.0", "crates/one"),
+                ("two", "1.2.0", "crates/two")
+            ],
+            found
+        );
+        assert_eq!(vec!["one".to_string()], packages[1].dependencies);
+    }
+
+    #[test]
+    fn test_packages_includes_root_package() {
+        let root = create_test_workspace("root-package");
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"root\"\nversion = \"2.0.0\"\n\n[workspace]\nmembers = [\"crates/two\"]\n",
+        )
+        .unwrap();
+
+        let packages = Workspace::new(&root.join("Cargo.toml"))
+            .unwrap()
+            .packages()
+            .unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        let found: Vec<_> = packages
+            .iter()
+            .map(|package| {
+                (
+                    package.name.as_str(),
+                    package.version.as_str(),
+                    package.member.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![("root", "2.0.0", "."), ("two", "1.2.0", "crates/two")],
+            found
+        );
+    }
+
+    #[test]
+    fn test_discover_from_member_directory() {
+        let root = create_test_workspace("discover");
+
+        let workspace = Workspace::discover(&root.join("crates").join("two"));
+        let _ = fs::remove_dir_all(&root);
+
+        let workspace = workspace.unwrap();
+        assert_eq!(root.canonicalize().ok(), workspace.root.canonicalize().ok());
+    }
+
+    #[test]
+    fn test_discover_stops_at_repository_root() {
+        let root = create_test_workspace("not-workspace");
+        let repo = root.join("crates").join("two");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+
+        let result = Workspace::discover(&repo);
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(matches!(result, Err(Error::NotWorkspace)));
+    }
+
+    #[test]
+    fn test_missing_member_is_an_error() {
+        let root = create_test_workspace("missing");
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/missing\"]\n",
+        )
+        .unwrap();
+
+        let result = Workspace::new(&root.join("Cargo.toml")).unwrap().packages();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case::nested("./crates/one", ".", "crates/one")]
+    #[case::no_dot("crates/one", ".", "crates/one")]
+    #[case::absolute("/repo/crates/one", "/repo", "crates/one")]
+    fn test_relative_member(#[case] path: &str, #[case] root: &str, #[case] expected: &str) {
+        assert_eq!(expected, relative_member(Path::new(path), Path::new(root)));
+    }
+
+    #[rstest]
+    #[case::exact("crates/old", true)]
+    #[case::inside("crates/old/nested", true)]
+    #[case::prefix_only("crates/older", false)]
+    #[case::other("crates/one", false)]
+    fn test_is_excluded(#[case] member: &str, #[case] expected: bool) {
+        let exclude = vec!["crates/old/".to_string()];
+
+        assert_eq!(expected, is_excluded(member, &exclude));
+    }
 }
@@ -1,5 +1,5 @@
 use core::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{fs, process::Command};
 
 use rstest::rstest;
@@ -1551,3 +1551,1957 @@ fn test_force_prefix_isolation_multi_tag_repo() {
 
     assert_eq!("patch\n0.1.1\n", test_result);
 }
+
+/// A `BREAKING CHANGE:` footer in the commit body must be detected even when
+/// the summary line carries no `!` marker.
+#[rstest]
+#[case::non_production_footer(
+    "v0.1.0",
+    "feat: new api\n\nBREAKING CHANGE: removed X",
+    "minor\n0.2.0\n"
+)]
+#[case::production_footer(
+    "v1.1.0",
+    "feat: new api\n\nBREAKING CHANGE: removed X",
+    "major\n2.0.0\n"
+)]
+#[case::production_hyphen_footer(
+    "v1.1.0",
+    "fix: new api\n\nSome detail.\n\nBREAKING-CHANGE: removed X",
+    "major\n2.0.0\n"
+)]
+#[case::production_no_footer("v1.1.0", "feat: new api\n\nSome detail.", "minor\n1.2.0\n")]
+fn test_repo_with_breaking_change_footer(
+    #[case] current_version: &str,
+    #[case] message: &str,
+    #[case] expected: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory(current_version);
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("-n calculate", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+/// Commit types can be assigned to a level with `--type-level`, including
+/// `none` for types that should not trigger a release.
+#[rstest]
+#[case::default_mapping("", &["perf: faster", "docs: update"], "patch\n1.1.1\n")]
+#[case::perf_as_feature("--type-level perf=feature", &["perf: faster"], "minor\n1.2.0\n")]
+#[case::security_as_fix("--type-level security=fix", &["security: patch cve"], "patch\n1.1.1\n")]
+#[case::docs_no_release("--type-level docs=none", &["docs: update"], "none\n")]
+#[case::docs_ci_no_release(
+    "--type-level docs=none --type-level CI=none",
+    &["docs: update", "ci: pipeline"],
+    "none\n"
+)]
+#[case::no_release_with_fix("--type-level docs=none", &["docs: update", "fix: bug"], "patch\n1.1.1\n")]
+#[case::no_release_breaking("--type-level docs=none", &["docs!: drop old docs"], "major\n2.0.0\n")]
+fn test_repo_with_type_levels(
+    #[case] type_levels: &str,
+    #[case] messages: &[&str],
+    #[case] expected: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    for message in messages {
+        let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+        println!("commit result: {result:?}");
+    }
+
+    let test_result = execute_test(&format!("-n {type_levels} calculate"), &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+/// Type levels apply to non-production versions, where a breaking level
+/// increments the minor number.
+#[rstest]
+#[case::security_as_breaking("--type-level sec=breaking", &["sec: close hole"], "minor\n0.4.0\n")]
+#[case::breaking_marker("", &["feat!: new api"], "minor\n0.4.0\n")]
+#[case::perf_as_feature("--type-level perf=feature", &["perf: faster"], "patch\n0.3.1\n")]
+#[case::docs_no_release("--type-level docs=none", &["docs: update"], "none\n")]
+fn test_repo_with_type_levels_non_production(
+    #[case] type_levels: &str,
+    #[case] messages: &[&str],
+    #[case] expected: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v0.3.0");
+
+    for message in messages {
+        let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+        println!("commit result: {result:?}");
+    }
+
+    let test_result = execute_test(&format!("-n {type_levels} calculate"), &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+/// Commit types listed with `--no-release` contribute nothing to the bump.
+#[rstest]
+#[case::docs_ci_default("", &["docs: update", "ci: pipeline"], "patch\n1.1.1\n")]
+#[case::docs_ci_ignored("--no-release docs,ci", &["docs: update", "ci: pipeline"], "none\n")]
+#[case::repeated_flag(
+    "--no-release docs --no-release ci",
+    &["docs: update", "ci: pipeline"],
+    "none\n"
+)]
+#[case::with_fix("--no-release docs,ci", &["docs: update", "fix: bug"], "patch\n1.1.1\n")]
+#[case::with_feat("--no-release docs,ci", &["feat: add", "ci: pipeline"], "minor\n1.2.0\n")]
+#[case::breaking("--no-release docs,ci", &["ci!: drop old runner"], "major\n2.0.0\n")]
+fn test_repo_with_no_release_types(
+    #[case] no_release: &str,
+    #[case] messages: &[&str],
+    #[case] expected: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    for message in messages {
+        let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+        println!("commit result: {result:?}");
+    }
+
+    let test_result = execute_test(&format!("-n {no_release} calculate"), &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+/// Settings are read from `nextsv.toml` and the `nextsv` metadata in
+/// `Cargo.toml`, with command line options taking precedence.
+#[rstest]
+#[case::nextsv_toml_no_release(
+    &[("nextsv.toml", "no-release = [\"docs\"]\n")],
+    "v1.1.0",
+    "-n calculate",
+    "none\n"
+)]
+#[case::nextsv_toml_prefix(
+    &[("nextsv.toml", "prefix = \"app-v\"\n")],
+    "app-v1.1.0",
+    "-n calculate",
+    "patch\n1.1.1\n"
+)]
+#[case::nextsv_toml_type_levels(
+    &[("nextsv.toml", "[type-levels]\ndocs = \"feature\"\n")],
+    "v1.1.0",
+    "-n calculate",
+    "minor\n1.2.0\n"
+)]
+#[case::package_metadata(
+    &[(
+        "Cargo.toml",
+        "[package]\nname = \"app\"\nversion = \"1.1.0\"\n\n[package.metadata.nextsv]\ncheck = \"feature\"\n",
+    )],
+    "v1.1.0",
+    "-n calculate",
+    "none\n"
+)]
+#[case::workspace_metadata(
+    &[(
+        "Cargo.toml",
+        "[workspace]\nmembers = []\n\n[workspace.metadata.nextsv]\nno-release = [\"docs\"]\n",
+    )],
+    "v1.1.0",
+    "-n calculate",
+    "none\n"
+)]
+#[case::nextsv_toml_over_metadata(
+    &[
+        (
+            "Cargo.toml",
+            "[package]\nname = \"app\"\nversion = \"1.1.0\"\n\n[package.metadata.nextsv]\ncheck = \"feature\"\n",
+        ),
+        ("nextsv.toml", "check = \"other\"\n"),
+    ],
+    "v1.1.0",
+    "-n calculate",
+    "patch\n1.1.1\n"
+)]
+#[case::cli_over_nextsv_toml(
+    &[("nextsv.toml", "prefix = \"app-v\"\nno-release = [\"docs\"]\n")],
+    "v1.1.0",
+    "-n --type-level docs=fix calculate --prefix v",
+    "patch\n1.1.1\n"
+)]
+fn test_repo_with_config_file(
+    #[case] config_files: &[(&str, &str)],
+    #[case] current_version: &str,
+    #[case] arguments: &str,
+    #[case] expected: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory(current_version);
+
+    for (name, content) in config_files {
+        fs::write(temp_dir.join(name), content).unwrap();
+    }
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "docs: update", None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(arguments, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+/// Lists given on the command line replace the lists of the config file and
+/// the `--no-` flags turn off the flags set in the config file.
+#[rstest]
+#[case::no_release(
+    "no-release = [\"docs\"]\n",
+    "--print-config --no-release ci calculate",
+    "no-release = [\"ci\"]",
+    "docs"
+)]
+#[case::include_path(
+    "include-paths = [\"docs/**\"]\n",
+    "--print-config --include-path src/** calculate",
+    "include-paths = [\"src/**\"]",
+    "docs/**"
+)]
+#[case::exclude_scope(
+    "exclude-scopes = [\"release\"]\n",
+    "--print-config --exclude-scope ci calculate",
+    "exclude-scopes = [\"ci\"]",
+    "release"
+)]
+#[case::required_files(
+    "required-files = [\"CHANGELOG.md\"]\n",
+    "--print-config require -f README.md feature",
+    "required-files = [\"README.md\"]",
+    "CHANGELOG.md"
+)]
+#[case::lockstep(
+    "lockstep = true\n",
+    "--print-config workspace --no-lockstep",
+    "prefix = \"v\"",
+    "lockstep"
+)]
+#[case::annotated_only(
+    "annotated-only = true\n",
+    "--print-config --no-annotated-only calculate",
+    "prefix = \"v\"",
+    "annotated-only"
+)]
+#[case::ignore_root_files(
+    "ignore-root-files = true\n",
+    "--print-config --no-ignore-root-files calculate",
+    "prefix = \"v\"",
+    "ignore-root-files"
+)]
+fn test_repo_cli_overrides_config_file(
+    #[case] config_file: &str,
+    #[case] arguments: &str,
+    #[case] present: &str,
+    #[case] absent: &str,
+) {
+    let (temp_dir, _repo) = git_utils::create_test_git_directory("v1.1.0");
+    fs::write(temp_dir.join("nextsv.toml"), config_file).unwrap();
+
+    let test_result = execute_test(arguments, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(test_result.contains(present), "missing `{present}`");
+    assert!(!test_result.contains(absent), "unexpected `{absent}`");
+}
+
+#[test]
+fn test_repo_print_config() {
+    let (temp_dir, _repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    fs::write(
+        temp_dir.join("nextsv.toml"),
+        "required-files = [\"CHANGELOG.md\"]\n[type-levels]\nperf = \"fix\"\n",
+    )
+    .unwrap();
+
+    let test_result = execute_test("--print-config --check feature calculate", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "prefix = \"v\"\nrequired-files = [\"CHANGELOG.md\"]\nenforce-level = \"other\"\ncheck = \"feature\"\n\n[type-levels]\nperf = \"fix\"\n",
+        test_result
+    );
+}
+
+/// `--output json` reports the full calculation as a JSON object.
+#[test]
+fn test_repo_json_output() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    for message in [
+        "fix: bug",
+        "feat: new api",
+        "fix(deps): update serde to v2.0.0",
+    ] {
+        let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+        println!("commit result: {result:?}");
+    }
+
+    let test_result = execute_test("--output json calculate", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let report: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    // The date of the lightweight tag is the time the test commit was made
+    assert!(report["current_tag_date"].is_string());
+    let expected = serde_json::json!({
+        "current_version": "1.1.0",
+        "current_tag": "v1.1.0",
+        "current_tag_annotated": false,
+        "current_tag_tagger": null,
+        "current_tag_date": report["current_tag_date"],
+        "current_tag_message": null,
+        "next_version": "1.2.0",
+        "next_tag": "v1.2.0",
+        "bump": "minor",
+        "change_bump": "minor",
+        "route": "production",
+        "top_type": "feature",
+        "commit_counts": {"feat": 1, "fix": 2},
+        "breaking": false,
+        "major_dep_bumps": ["update serde to v2.0.0"],
+        "missing_files": [],
+    });
+
+    assert_eq!(expected, report);
+}
+
+#[rstest]
+#[case::missing_required_file(
+    "-o json require -f first-file feature",
+    "feat: new api",
+    "none",
+    serde_json::Value::Null,
+    serde_json::json!(["first-file"])
+)]
+#[case::no_release(
+    "-o json --no-release docs calculate",
+    "docs: update",
+    "none",
+    serde_json::Value::Null,
+    serde_json::json!([])
+)]
+#[case::ignores_report_flags(
+    "-b -o json calculate",
+    "fix: bug",
+    "patch",
+    serde_json::json!("1.1.1"),
+    serde_json::json!([])
+)]
+fn test_repo_json_output_cases(
+    #[case] arguments: &str,
+    #[case] message: &str,
+    #[case] expected_bump: &str,
+    #[case] expected_version: serde_json::Value,
+    #[case] expected_missing: serde_json::Value,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(arguments, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let report: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    assert_eq!(expected_bump, report["bump"]);
+    assert_eq!(expected_version, report["next_version"]);
+    assert_eq!(expected_missing, report["missing_files"]);
+}
+
+fn execute_test_with_env(
+    arguments: &str,
+    temp_dir: &PathBuf,
+    github_output: Option<&str>,
+) -> String {
+    let test_args: Vec<&str> = arguments.split_ascii_whitespace().collect();
+    println!("test_args: {test_args:?}");
+
+    let mut command = Command::new(snapbox::cmd::cargo_bin!("nextsv"));
+    command.args(test_args).current_dir(temp_dir);
+    match github_output {
+        Some(path) => command.env("GITHUB_OUTPUT", path),
+        None => command.env_remove("GITHUB_OUTPUT"),
+    };
+    let output = command.output().unwrap();
+
+    println!("Exit code: {}", output.status.code().unwrap());
+    let test_result = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    println!("stdout:\n-------\n{test_result}");
+    println!("stderr:\n-------\n{stderr}");
+    test_result
+}
+
+/// `--output env` writes `key=value` lines to stdout, to `--output-file`
+/// or to the file named by `GITHUB_OUTPUT`, appending to existing content.
+#[rstest]
+#[case::stdout("-o env calculate", None, None)]
+#[case::output_file("-o env --output-file out.env calculate", None, Some("out.env"))]
+#[case::github_output("-o env calculate", Some("github.out"), Some("github.out"))]
+#[case::output_file_over_github_output(
+    "-o env --output-file out.env calculate",
+    Some("github.out"),
+    Some("out.env")
+)]
+fn test_repo_env_output(
+    #[case] arguments: &str,
+    #[case] github_output: Option<&str>,
+    #[case] expected_file: Option<&str>,
+) {
+    let expected = "bump=minor\nversion=1.2.0\ntag=v1.2.0\nreleased=true\nprevious_version=1.1.0\n";
+    let existing = "existing=value\n";
+
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", None);
+    println!("commit result: {result:?}");
+
+    if let Some(file) = expected_file {
+        fs::write(temp_dir.join(file), existing).unwrap();
+    }
+
+    let test_result = execute_test_with_env(arguments, &temp_dir, github_output);
+
+    let file_content = expected_file.map(|file| fs::read_to_string(temp_dir.join(file)).unwrap());
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    match file_content {
+        Some(content) => {
+            assert_eq!("", test_result);
+            assert_eq!(format!("{existing}{expected}"), content);
+        }
+        None => assert_eq!(expected, test_result),
+    }
+}
+
+#[test]
+fn test_repo_env_output_no_release() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "docs: update", None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test_with_env("-o env --no-release docs calculate", &temp_dir, None);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "bump=none\nversion=\ntag=\nreleased=false\nprevious_version=1.1.0\n",
+        test_result
+    );
+}
+
+#[test]
+fn test_repo_from_subdirectory() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    fs::create_dir_all(temp_dir.join("src")).unwrap();
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "feat: new api",
+        Some("src/lib.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("calculate", &temp_dir.join("src"));
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!("minor\n", test_result);
+}
+
+#[rstest]
+#[case::repository_root("")]
+#[case::repository_subdirectory("/src")]
+fn test_repo_repository_path(#[case] subdirectory: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    fs::create_dir_all(temp_dir.join("src")).unwrap();
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "feat: new api",
+        Some("src/lib.rs"),
+    );
+    println!("commit result: {result:?}");
+    let repo_path = temp_dir.canonicalize().unwrap();
+    let outside = std::env::temp_dir();
+
+    let test_result = execute_test(
+        &format!("--repo {}{subdirectory} calculate", repo_path.display()),
+        &outside,
+    );
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!("minor\n", test_result);
+}
+
+#[rstest]
+#[case::default("-n calculate", "minor\n1.2.0\n")]
+#[case::from_revision("-n calculate --from HEAD~1", "patch\n1.1.1\n")]
+#[case::to_revision("-n calculate --to HEAD~1", "minor\n1.2.0\n")]
+#[case::empty_range("-n calculate --from HEAD~1 --to HEAD~1", "none\n")]
+#[case::from_version_tag("-n calculate --from v1.1.0 --to HEAD~2", "patch\n1.1.1\n")]
+fn test_repo_commit_range(#[case] args: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    for (message, file) in [
+        ("fix: first typo", "one.rs"),
+        ("feat: new api", "two.rs"),
+        ("fix: second typo", "three.rs"),
+    ] {
+        let result =
+            git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, Some(file));
+        println!("commit result: {result:?}");
+    }
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+#[test]
+fn test_repo_commit_range_from_earlier_version_tag() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some("one.rs"));
+    println!("commit result: {result:?}");
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.2.0", commit.as_object(), false)
+        .unwrap();
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", Some("two.rs"));
+    println!("commit result: {result:?}");
+
+    let latest = execute_test("-n calculate", &temp_dir);
+    let earlier = execute_test("-n calculate --from v1.1.0", &temp_dir);
+    let explained = execute_test("explain --from v1.1.0", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!("patch\n1.2.1\n", latest);
+    assert_eq!("minor\n1.2.0\n", earlier);
+    assert!(explained.contains("Commits counted from `v1.1.0` to `HEAD`"));
+}
+
+#[rstest]
+#[case::highest_reachable("-n calculate", "patch\n1.5.1\n")]
+#[case::highest_selected("--tag-selection highest -n calculate", "patch\n1.5.1\n")]
+#[case::nearest("--tag-selection nearest -n calculate", "patch\n1.1.2\n")]
+#[case::any("--tag-selection any -n calculate", "patch\n2.0.1\n")]
+fn test_repo_tag_selection(#[case] args: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    let initial = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.5.0", initial.as_object(), false)
+        .unwrap();
+
+    // A release on the main branch that is not reachable from the maintenance branch
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "feat!: new api",
+        Some("api.rs"),
+    );
+    println!("commit result: {result:?}");
+    let main = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v2.0.0", main.as_object(), false)
+        .unwrap();
+
+    repo.branch("release", &initial, false).unwrap();
+    repo.set_head("refs/heads/release").unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix: first typo",
+        Some("one.rs"),
+    );
+    println!("commit result: {result:?}");
+    let fix = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.1.1", fix.as_object(), false)
+        .unwrap();
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix: second typo",
+        Some("two.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+/// Create an annotated tag on HEAD with the tagger date `seconds` in UTC+01:00.
+fn tag_annotated(repo: &git2::Repository, name: &str, seconds: i64, message: &str) {
+    let head = git_utils::find_last_commit(repo).unwrap();
+    let tagger = git2::Signature::new(
+        "Tester",
+        "tester@example.com",
+        &git2::Time::new(seconds, 60),
+    )
+    .unwrap();
+    repo.tag(name, head.as_object(), &tagger, message, false)
+        .unwrap();
+}
+
+#[rstest]
+#[case::all_tags("-n calculate", "patch\n1.2.1\n")]
+#[case::annotated_only("--annotated-only -n calculate", "minor\n1.2.0\n")]
+fn test_repo_annotated_only(#[case] args: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    tag_annotated(&repo, "v1.1.0", 1_700_000_000, "Release 1.1.0");
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some("api.rs"));
+    println!("commit result: {result:?}");
+    // A stray lightweight tag that is not part of the release process
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.2.0", commit.as_object(), false)
+        .unwrap();
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", Some("one.rs"));
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+#[rstest]
+#[case::highest("-n calculate", "patch\n2.0.1\n")]
+#[case::latest("--tag-selection latest -n calculate", "patch\n1.3.1\n")]
+fn test_repo_tag_selection_latest(#[case] args: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    tag_annotated(&repo, "v2.0.0", 1_700_000_000, "Release 2.0.0");
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", Some("one.rs"));
+    println!("commit result: {result:?}");
+    tag_annotated(&repo, "v1.3.0", 1_700_086_400, "Release 1.3.0");
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: other", Some("two.rs"));
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+#[test]
+fn test_repo_annotated_tag_details() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    tag_annotated(
+        &repo,
+        "v1.1.0",
+        1_700_000_000,
+        "Release 1.1.0\n\nFirst stable API.\n",
+    );
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", Some("one.rs"));
+    println!("commit result: {result:?}");
+
+    let explained = execute_test("explain", &temp_dir);
+    let test_result = execute_test("--output json calculate", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(
+        explained.contains("Tagged: 2023-11-14 23:13:20 +0100 by Tester <tester@example.com>\n")
+    );
+    assert!(explained.contains("Tag message: Release 1.1.0\n"));
+
+    let report: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    assert_eq!(true, report["current_tag_annotated"]);
+    assert_eq!("Tester <tester@example.com>", report["current_tag_tagger"]);
+    assert_eq!("2023-11-14 23:13:20 +0100", report["current_tag_date"]);
+    assert_eq!(
+        "Release 1.1.0\n\nFirst stable API.",
+        report["current_tag_message"]
+    );
+}
+
+#[rstest]
+#[case::default_version("-n calculate", None, "0.1.0\n0.1.0\n")]
+#[case::initial_version("--initial-version 0.0.1 -n calculate", None, "0.0.1\n0.0.1\n")]
+#[case::manifest_version("-n calculate", Some("0.3.0"), "0.3.0\n0.3.0\n")]
+#[case::initial_version_over_manifest(
+    "--initial-version 1.0.0 -n calculate",
+    Some("0.3.0"),
+    "1.0.0\n1.0.0\n"
+)]
+fn test_repo_first_version(
+    #[case] args: &str,
+    #[case] manifest_version: Option<&str>,
+    #[case] expected: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    repo.tag_delete("initial").unwrap();
+    if let Some(version) = manifest_version {
+        fs::write(
+            temp_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"one\"\nversion = \"{version}\"\n"),
+        )
+        .unwrap();
+    }
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some("api.rs"));
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+/// With tags in the repository but no version tag the first version is only
+/// calculated if the initial version is set.
+#[rstest]
+#[case::no_initial_version("-n calculate", None)]
+#[case::initial_version("--initial-version 0.2.0 -n calculate", Some("0.2.0\n0.2.0\n"))]
+#[case::force("--initial-version 0.2.0 -n force patch", None)]
+fn test_repo_first_version_with_other_tags(#[case] args: &str, #[case] expected: Option<&str>) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[package]\nname = \"one\"\nversion = \"0.3.0\"\n",
+    )
+    .unwrap();
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some("api.rs"));
+    println!("commit result: {result:?}");
+
+    let (exit_code, test_result) = execute_test_with_exit_code(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    match expected {
+        Some(expected) => {
+            assert_eq!(0, exit_code);
+            assert_eq!(expected, test_result);
+        }
+        None => assert_ne!(0, exit_code),
+    }
+}
+
+#[test]
+fn test_repo_first_version_tag_exists() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v0.3.0");
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some("api.rs"));
+    println!("commit result: {result:?}");
+
+    // The lightweight tag is not a version tag but its name is taken
+    let output = Command::new(snapbox::cmd::cargo_bin!("nextsv"))
+        .args([
+            "--annotated-only",
+            "--initial-version",
+            "0.3.0",
+            "calculate",
+        ])
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    println!("stderr: {stderr}");
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("The tag `v0.3.0` for the first version already exists"));
+}
+
+#[test]
+fn test_repo_first_version_explain() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    repo.tag_delete("initial").unwrap();
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", Some("one.rs"));
+    println!("commit result: {result:?}");
+
+    let explained = execute_test("explain", &temp_dir);
+    let test_result = execute_test("--output json calculate", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(explained.contains("Current tag: none"));
+    assert!(explained.contains("chore: initial commit"));
+    assert!(explained.contains("No version tag found; commits counted from the root commit"));
+    assert!(explained.contains("First version is `0.1.0` with tag `v0.1.0`"));
+
+    let report: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    assert_eq!("", report["current_version"]);
+    assert_eq!("0.1.0", report["next_version"]);
+    assert_eq!("v0.1.0", report["next_tag"]);
+}
+
+#[rstest]
+#[case::suffix(
+    "release-1.1.0-final",
+    "--tag-template release-{version}-final --output json calculate",
+    "1.1.0",
+    "release-1.2.0-final"
+)]
+#[case::suffix_ignores_other_tags(
+    "release-1.1.0-final",
+    "--tag-template release-{version}-draft --initial-version 0.1.0 --output json calculate",
+    "",
+    "release-0.1.0-draft"
+)]
+#[case::prefix_with_dot("v.1.1.0", "--output json calculate -p v.", "1.1.0", "v.1.2.0")]
+fn test_repo_tag_template(
+    #[case] tag: &str,
+    #[case] args: &str,
+    #[case] current_version: &str,
+    #[case] next_tag: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory(tag);
+
+    // The dot of a prefix is not a wildcard so `vx2.0.0` is not a version tag for `v.`
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("vx2.0.0", commit.as_object(), false)
+        .unwrap();
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some("api.rs"));
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let report: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    assert_eq!(current_version, report["current_version"]);
+    assert_eq!(next_tag, report["next_tag"]);
+}
+
+#[rstest]
+#[case::bump_version_and_tag("-n -t calculate", "minor\n1.2.0\nv1.2.0\n")]
+#[case::tag_only("-b -t calculate", "v1.2.0\n")]
+#[case::template_suffix(
+    "--tag-template v{version}-final --initial-version 0.1.0 -b -t calculate",
+    "v0.1.0-final\n"
+)]
+#[case::package("-b -t calculate -k one", "one-v0.1.1\n")]
+#[case::package_template(
+    "--tag-template {package}@{version} --initial-version 0.1.0 -b -t calculate -k one",
+    "one@0.1.0\n"
+)]
+fn test_repo_report_tag(#[case] args: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "feat: new api",
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+#[test]
+fn test_repo_report_tag_no_release() {
+    let (temp_dir, _repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let test_result = execute_test("-n -t calculate", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!("none\n", test_result);
+}
+
+/// Add tags that look like version tags but do not hold a valid version.
+fn add_malformed_tags(repo: &git2::Repository) {
+    let commit = git_utils::find_last_commit(repo).unwrap();
+    for tag in ["v1.2", "v1.2.3.4", "v99999999999.0.0", "vnext"] {
+        repo.tag_lightweight(tag, commit.as_object(), false)
+            .unwrap();
+    }
+}
+
+#[rstest]
+#[case::calculate("-n calculate", "minor\n1.2.0\n")]
+#[case::explain_current_tag("explain", "Current tag: v1.1.0\n")]
+fn test_repo_malformed_tags_are_skipped(#[case] args: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_malformed_tags(&repo);
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(test_result.contains(expected));
+}
+
+#[test]
+fn test_repo_tags() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.0.0");
+    add_malformed_tags(&repo);
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("initial", commit.as_object(), false)
+        .unwrap();
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", None);
+    println!("commit result: {result:?}");
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.1.0", commit.as_object(), false)
+        .unwrap();
+
+    let test_result = execute_test("tags", &temp_dir);
+    let from_first = execute_test("tags --to v1.0.0", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "tag               version  status    reason
+initial           -        rejected  does not match `v{version}`
+v1.0.0            1.0.0    accepted  version tag
+v1.1.0            1.1.0    current   current version tag by the `highest` selection
+v1.2              -        rejected  invalid version: Version must have three components but only 2 found
+v1.2.3.4          -        rejected  invalid version: Version must have three components but at least 4 were found
+v99999999999.0.0  -        rejected  invalid version: Version component 99999999999 is too large
+vnext             -        rejected  does not match `v{version}`
+",
+        test_result
+    );
+    assert!(from_first.contains(
+        "v1.0.0            1.0.0    current   current version tag by the `highest` selection\n"
+    ));
+    assert!(from_first
+        .contains("v1.1.0            -        rejected  not reachable from the start commit\n"));
+}
+
+#[test]
+fn test_repo_tags_json() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_malformed_tags(&repo);
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.0.0", commit.as_object(), false)
+        .unwrap();
+
+    let test_result = execute_test("--annotated-only --output json tags", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let tags: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    assert_eq!("v1.0.0", tags[0]["tag"]);
+    assert_eq!("rejected", tags[0]["status"]);
+    assert_eq!(
+        "lightweight tag; only annotated tags are considered",
+        tags[0]["reason"]
+    );
+    assert_eq!("v1.2", tags[2]["tag"]);
+    assert_eq!(serde_json::Value::Null, tags[2]["version"]);
+    assert_eq!(
+        "invalid version: Version must have three components but only 2 found",
+        tags[2]["reason"]
+    );
+}
+
+#[test]
+fn test_repo_list() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v0.1.0");
+    let mut commits = vec![git_utils::find_last_commit(&repo).unwrap().id().to_string()];
+    for (message, tag) in [
+        ("fix: typo", Some("v0.1.1")),
+        ("feat: new api", None),
+        ("docs: describe the api", Some("v0.2.0")),
+        ("feat!: remove the old api", Some("v1.0.0")),
+        ("fix: unreleased", None),
+    ] {
+        let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+        println!("commit result: {result:?}");
+        if let Some(tag) = tag {
+            let commit = git_utils::find_last_commit(&repo).unwrap();
+            repo.tag_lightweight(tag, commit.as_object(), false)
+                .unwrap();
+            commits.push(commit.id().to_string());
+        }
+    }
+    add_malformed_tags(&repo);
+
+    let test_result = execute_test("list", &temp_dir);
+    let json_result = execute_test("--output json list", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let rows: Vec<Vec<&str>> = test_result
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(vec!["tag", "commit", "date", "bump", "commits"], rows[0]);
+    assert_eq!(5, rows.len());
+    for (row, (tag, bump)) in rows[1..].iter().zip([
+        ("v0.1.0", "first"),
+        ("v0.1.1", "patch"),
+        ("v0.2.0", "minor"),
+        ("v1.0.0", "minor"),
+    ]) {
+        assert_eq!(tag, row[0]);
+        assert_eq!(bump, row[row.len() - 2]);
+    }
+
+    let releases: serde_json::Value = serde_json::from_str(&json_result).unwrap();
+    let releases = releases.as_array().unwrap();
+    assert_eq!(4, releases.len());
+    for (release, commit) in releases.iter().zip(&commits) {
+        assert_eq!(commit.as_str(), release["commit"]);
+        assert!(release["date"].is_string());
+    }
+    assert_eq!(serde_json::Value::Null, releases[0]["previous_version"]);
+    assert_eq!("0.1.1", releases[2]["previous_version"]);
+    assert_eq!("feature", releases[2]["top_type"]);
+    assert_eq!(2, releases[2]["commits"]);
+    assert_eq!("breaking", releases[3]["top_type"]);
+}
+
+#[test]
+fn test_repo_list_package() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    for (message, file, tag) in [
+        ("feat: new api", "crates/one/api.rs", "one-v0.2.0"),
+        ("fix: typo", "crates/two/lib.rs", "two-v0.1.1"),
+        ("fix: typo", "crates/one/fix.rs", "one-v0.2.1"),
+    ] {
+        let result =
+            git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, Some(file));
+        println!("commit result: {result:?}");
+        let commit = git_utils::find_last_commit(&repo).unwrap();
+        repo.tag_lightweight(tag, commit.as_object(), false)
+            .unwrap();
+    }
+
+    let test_result = execute_test("--output json list -k one", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let releases: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    let releases = releases.as_array().unwrap();
+    let summary: Vec<(&str, &str, u64)> = releases
+        .iter()
+        .map(|release| {
+            (
+                release["tag"].as_str().unwrap(),
+                release["bump"].as_str().unwrap(),
+                release["commits"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("one-v0.1.0", "first", 1),
+            ("one-v0.2.0", "minor", 1),
+            ("one-v0.2.1", "patch", 1),
+        ],
+        summary
+    );
+}
+
+#[test]
+fn test_repo_explain() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", None);
+    println!("commit result: {result:?}");
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "tidy the layout",
+        Some("layout.txt"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("--check breaking explain", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(test_result.contains("Current tag: v1.1.0\n"));
+    assert!(test_result.contains("Route: production\n"));
+    assert!(test_result.contains(" feature  feat: new api\n"));
+    assert!(test_result.contains(" other    tidy the layout (non-conventional)\n"));
+    assert!(test_result.contains("Threshold `breaking` not met by change level `feature`"));
+}
+
+#[test]
+fn test_repo_explain_outside_subdir() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("explain --subdir crates", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(test_result.contains(" skipped  fix: typo (outside subdir `crates`)\n"));
+    assert!(test_result.contains("No new version is required"));
+}
+
+#[rstest]
+#[case::excluded("--exclude-path docs/** calculate", "docs/guide.md", "none\n")]
+#[case::excluded_by_name("--exclude-path *.md calculate", "docs/guide.md", "none\n")]
+#[case::not_excluded("--exclude-path docs/** calculate", "src/lib.rs", "minor\n")]
+#[case::not_included("--include-path src/** calculate", "docs/guide.md", "none\n")]
+#[case::included("--include-path src/** calculate", "src/lib.rs", "minor\n")]
+fn test_repo_path_globs(#[case] args: &str, #[case] file: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    fs::create_dir_all(temp_dir.join(file).parent().unwrap()).unwrap();
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some(file));
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+#[rstest]
+#[case::excluded("--exclude-scope docs calculate", "feat(docs): new guide", "none\n")]
+#[case::excluded_case_insensitive(
+    "--exclude-scope docs calculate",
+    "feat(Docs): new guide",
+    "none\n"
+)]
+#[case::not_excluded("--exclude-scope docs calculate", "feat(parser): new api", "minor\n")]
+#[case::included(
+    "--include-scope parser,lexer calculate",
+    "feat(lexer): new api",
+    "minor\n"
+)]
+#[case::not_included("--include-scope parser calculate", "feat(cli): new api", "none\n")]
+#[case::no_scope_not_included("--include-scope parser calculate", "feat: new api", "none\n")]
+fn test_repo_scopes(#[case] args: &str, #[case] message: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(args, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+#[test]
+fn test_repo_explain_excluded_paths() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "docs: update readme",
+        Some("README.md"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("--exclude-path *.md explain", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert!(test_result.contains(" skipped  docs: update readme (only excluded paths changed)\n"));
+    assert!(test_result.contains("No new version is required"));
+}
+
+#[test]
+fn test_repo_apply() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "tester").unwrap();
+    config.set_str("user.email", "tester@example.net").unwrap();
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", None);
+    println!("commit result: {result:?}");
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[package]\nname = \"one\"\nversion = \"1.1.0\" # release\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.join("Cargo.lock"),
+        "version = 4\n\n[[package]]\nname = \"one\"\nversion = \"1.1.0\"\n",
+    )
+    .unwrap();
+
+    let test_result = execute_test("apply --message release-{tag}", &temp_dir);
+    let manifest = fs::read_to_string(temp_dir.join("Cargo.toml")).unwrap();
+    let lock = fs::read_to_string(temp_dir.join("Cargo.lock")).unwrap();
+    let head = git_utils::find_last_commit(&repo).unwrap();
+    let tag = repo.revparse_single("v1.2.0").map(|tag| tag.id()).ok();
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "Committed `release-v1.2.0` and created tag `v1.2.0`\n",
+        test_result
+    );
+    assert_eq!(
+        "[package]\nname = \"one\"\nversion = \"1.2.0\" # release\n",
+        manifest
+    );
+    assert!(lock.contains("version = \"1.2.0\"\n"));
+    assert_eq!(Some("release-v1.2.0"), head.message().ok());
+    assert_eq!(Some(head.id()), tag);
+}
+
+#[test]
+fn test_repo_apply_annotated_only() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "tester").unwrap();
+    config.set_str("user.email", "tester@example.net").unwrap();
+    tag_annotated(&repo, "v1.1.0", 1_700_000_000, "Release 1.1.0");
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "feat: new api", Some("api.rs"));
+    println!("commit result: {result:?}");
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[package]\nname = \"one\"\nversion = \"1.1.0\"\n",
+    )
+    .unwrap();
+
+    let apply_result = execute_test("--annotated-only apply", &temp_dir);
+    let tag_kind = repo.revparse_single("v1.2.0").map(|tag| tag.kind()).ok();
+    let calculate_result = execute_test("--annotated-only calculate", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "Committed `chore: release 1.2.0` and created tag `v1.2.0`\n",
+        apply_result
+    );
+    assert_eq!(Some(Some(git2::ObjectType::Tag)), tag_kind);
+    assert_eq!("none\n", calculate_result);
+}
+
+#[test]
+fn test_repo_apply_tag_exists() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "tester").unwrap();
+    config.set_str("user.email", "tester@example.net").unwrap();
+    tag_annotated(&repo, "v1.1.0", 1_700_000_000, "Release 1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", None);
+    println!("commit result: {result:?}");
+    // A lightweight tag is not a version tag with --annotated-only but its name is taken
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.1.1", commit.as_object(), false)
+        .unwrap();
+    let manifest = "[package]\nname = \"one\"\nversion = \"1.1.0\"\n";
+    fs::write(temp_dir.join("Cargo.toml"), manifest).unwrap();
+
+    let (exit_code, _) = execute_test_with_exit_code("--annotated-only apply", &temp_dir);
+    let after = fs::read_to_string(temp_dir.join("Cargo.toml")).unwrap();
+    let head = git_utils::find_last_commit(&repo).unwrap();
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_ne!(0, exit_code);
+    assert_eq!(manifest, after);
+    assert_eq!(commit.id(), head.id());
+}
+
+#[test]
+fn test_repo_apply_leaves_staged_files_out() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "tester").unwrap();
+    config.set_str("user.email", "tester@example.net").unwrap();
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", None);
+    println!("commit result: {result:?}");
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[package]\nname = \"one\"\nversion = \"1.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.join("notes.txt"), "work in progress\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("notes.txt")).unwrap();
+    index.write().unwrap();
+
+    let test_result = execute_test("apply", &temp_dir);
+    let tree = git_utils::find_last_commit(&repo).unwrap().tree().unwrap();
+    let manifest_status = repo.status_file(Path::new("Cargo.toml")).unwrap();
+    let notes_status = repo.status_file(Path::new("notes.txt")).unwrap();
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "Committed `chore: release 1.1.1` and created tag `v1.1.1`\n",
+        test_result
+    );
+    assert!(tree.get_name("Cargo.toml").is_some());
+    assert!(tree.get_name("notes.txt").is_none());
+    assert!(manifest_status.is_empty());
+    assert_eq!(git2::Status::INDEX_NEW, notes_status);
+}
+
+#[test]
+fn test_repo_apply_repository_path() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "tester").unwrap();
+    config.set_str("user.email", "tester@example.net").unwrap();
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", None);
+    println!("commit result: {result:?}");
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[package]\nname = \"one\"\nversion = \"1.1.0\"\n",
+    )
+    .unwrap();
+    let repo_path = temp_dir.canonicalize().unwrap();
+    let outside = std::env::temp_dir();
+
+    let test_result = execute_test(&format!("--repo {} apply", repo_path.display()), &outside);
+    let manifest = fs::read_to_string(temp_dir.join("Cargo.toml")).unwrap();
+    let tag = repo.revparse_single("v1.1.1").map(|tag| tag.id()).ok();
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "Committed `chore: release 1.1.1` and created tag `v1.1.1`\n",
+        test_result
+    );
+    assert_eq!("[package]\nname = \"one\"\nversion = \"1.1.1\"\n", manifest);
+    assert!(tag.is_some());
+}
+
+#[test]
+fn test_repo_apply_dry_run() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", None);
+    println!("commit result: {result:?}");
+    let manifest = "[package]\nname = \"one\"\nversion = \"1.1.0\"\n";
+    fs::write(temp_dir.join("Cargo.toml"), manifest).unwrap();
+
+    let test_result = execute_test("apply --dry-run", &temp_dir);
+    let after = fs::read_to_string(temp_dir.join("Cargo.toml")).unwrap();
+    let tag = repo.revparse_single("v1.1.1");
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "--- a/Cargo.toml\n+++ b/Cargo.toml\n@@ -3 +3 @@\n-version = \"1.1.0\"\n+version = \"1.1.1\"\nDry run: would commit `chore: release 1.1.1` and create tag `v1.1.1`\n",
+        test_result
+    );
+    assert_eq!(manifest, after);
+    assert!(tag.is_err());
+}
+
+#[test]
+fn test_repo_apply_dry_run_unchanged_manifest() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+
+    let result = git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", None);
+    println!("commit result: {result:?}");
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[package]\nname = \"one\"\nversion = \"1.1.1\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.join("Cargo.lock"),
+        "version = 4\n\n[[package]]\nname = \"one\"\nversion = \"1.1.0\"\n",
+    )
+    .unwrap();
+
+    let test_result = execute_test("apply --dry-run", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "Dry run: would commit `chore: release 1.1.1` and create tag `v1.1.1`\n",
+        test_result
+    );
+}
+
+/// Add a workspace of packages `one` and `two`, each tagged at `0.1.0`.
+/// The `dependencies` are added to the manifest of `two`.
+fn add_test_workspace(repo: &git2::Repository, temp_dir: &Path, dependencies: &str) {
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/one\", \"crates/two\"]\n",
+    )
+    .unwrap();
+    for package in ["one", "two"] {
+        let member = temp_dir.join("crates").join(package);
+        fs::create_dir_all(&member).unwrap();
+        fs::write(
+            member.join("Cargo.toml"),
+            format!("[package]\nname = \"{package}\"\nversion = \"0.1.0\"\n{dependencies}"),
+        )
+        .unwrap();
+    }
+    let result = git_utils::create_file_and_commit(
+        repo,
+        temp_dir.to_path_buf(),
+        "chore: add workspace",
+        Some("crates/one/lib.rs"),
+    );
+    println!("commit result: {result:?}");
+    let commit = git_utils::find_last_commit(repo).unwrap();
+    for tag in ["one-v0.1.0", "two-v0.1.0"] {
+        repo.tag_lightweight(tag, commit.as_object(), false)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_repo_workspace() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "feat!: new api",
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("workspace", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "package  current  bump   next\none      0.1.0    minor  0.2.0\ntwo      0.1.0    none   -\n",
+        test_result
+    );
+}
+
+#[test]
+fn test_repo_workspace_first_version() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    repo.tag_delete("two-v0.1.0").unwrap();
+    fs::write(
+        temp_dir.join("crates").join("two").join("Cargo.toml"),
+        "[package]\nname = \"two\"\nversion = \"0.3.0\"\n",
+    )
+    .unwrap();
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix: typo",
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let manifest_result = execute_test("workspace", &temp_dir);
+    let test_result = execute_test("--initial-version 0.1.0 workspace", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "package  current  bump   next\none      0.1.0    patch  0.1.1\ntwo      -        0.3.0  0.3.0\n",
+        manifest_result
+    );
+    assert_eq!(
+        "package  current  bump   next\none      0.1.0    patch  0.1.1\ntwo      -        0.1.0  0.1.0\n",
+        test_result
+    );
+}
+
+#[test]
+fn test_repo_workspace_first_version_inherited() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    repo.tag_delete("two-v0.1.0").unwrap();
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/one\", \"crates/two\"]\n\n[workspace.package]\nversion = \"0.4.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.join("crates").join("two").join("Cargo.toml"),
+        "[package]\nname = \"two\"\nversion.workspace = true\n",
+    )
+    .unwrap();
+
+    let test_result = execute_test("workspace", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "package  current  bump   next\none      0.1.0    none   -\ntwo      -        0.4.0  0.4.0\n",
+        test_result
+    );
+}
+
+#[test]
+fn test_repo_workspace_tags_on_different_commits() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+
+    for (message, file) in [
+        ("fix: typo", "crates/one/api.rs"),
+        ("feat: new api", "crates/two/api.rs"),
+    ] {
+        let result =
+            git_utils::create_file_and_commit(&repo, temp_dir.clone(), message, Some(file));
+        println!("commit result: {result:?}");
+    }
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("one-v0.1.1", commit.as_object(), false)
+        .unwrap();
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix: typo",
+        Some("crates/two/lib.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("workspace", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "package  current  bump   next\none      0.1.1    none   -\ntwo      0.1.0    patch  0.1.1\n",
+        test_result
+    );
+}
+
+#[test]
+fn test_repo_workspace_from_member_directory() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix: typo",
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("workspace", &temp_dir.join("crates").join("two"));
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "package  current  bump   next\none      0.1.0    patch  0.1.1\ntwo      0.1.0    none   -\n",
+        test_result
+    );
+}
+
+#[test]
+fn test_repo_workspace_json() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix: typo",
+        Some("crates/two/lib.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("--output json workspace", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let reports: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    assert_eq!("one", reports[0]["package"]);
+    assert_eq!("none", reports[0]["bump"]);
+    assert_eq!("two", reports[1]["package"]);
+    assert_eq!("crates/two", reports[1]["member"]);
+    assert_eq!("two-v0.1.1", reports[1]["next_tag"]);
+}
+
+#[rstest]
+#[case::package_at_version("{package}@{version}", ["one@0.1.0", "two@0.1.0"], "two@0.1.1")]
+#[case::path_and_version(
+    "{path}/v{version}",
+    ["crates/one/v0.1.0", "crates/two/v0.1.0"],
+    "crates/two/v0.1.1"
+)]
+fn test_repo_workspace_tag_template(
+    #[case] template: &str,
+    #[case] tags: [&str; 2],
+    #[case] next_tag: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    for tag in tags {
+        repo.tag_lightweight(tag, commit.as_object(), false)
+            .unwrap();
+    }
+    for tag in ["one-v0.1.0", "two-v0.1.0"] {
+        repo.tag_delete(tag).unwrap();
+    }
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix: typo",
+        Some("crates/two/lib.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(
+        &format!("--tag-template {template} --output json workspace"),
+        &temp_dir,
+    );
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let reports: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    assert_eq!("one", reports[0]["package"]);
+    assert_eq!("0.1.0", reports[0]["current_version"]);
+    assert_eq!("none", reports[0]["bump"]);
+    assert_eq!("two", reports[1]["package"]);
+    assert_eq!("0.1.0", reports[1]["current_version"]);
+    assert_eq!(next_tag, reports[1]["next_tag"]);
+}
+
+#[rstest]
+#[case::root_lock_counts("Cargo.lock", "workspace", "", ["0.1.1", "0.1.1"])]
+#[case::root_lock_ignored("Cargo.lock", "--ignore-root-files workspace", "", ["-", "-"])]
+#[case::root_lock_excluded(
+    "Cargo.lock",
+    "workspace",
+    "[packages.two]\nexclude = [\"Cargo.lock\"]\n",
+    ["0.1.1", "-"]
+)]
+#[case::similar_path_not_counted("crates/one-extra/lib.rs", "workspace", "", ["-", "-"])]
+#[case::included_path(
+    "docs/one.md",
+    "workspace",
+    "[packages.one]\ninclude = [\"docs/one.md\"]\n",
+    ["0.1.1", "-"]
+)]
+fn test_repo_workspace_package_paths(
+    #[case] file: &str,
+    #[case] args: &str,
+    #[case] config: &str,
+    #[case] expected: [&str; 2],
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    fs::create_dir_all(temp_dir.join(file).parent().unwrap()).unwrap();
+    fs::write(temp_dir.join("nextsv.toml"), config).unwrap();
+
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: typo", Some(file));
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(&format!("--output json {args}"), &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let reports: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    for (report, expected) in reports.as_array().unwrap().iter().zip(expected) {
+        let next_version = report["next_version"].as_str().unwrap_or("-");
+        assert_eq!(expected, next_version, "{}", report["package"]);
+    }
+}
+
+#[rstest]
+#[case::scope_mapped("fix(parser): typo", ["0.1.1", "0.1.1"])]
+#[case::scope_not_mapped("fix(cli): typo", ["0.1.1", "-"])]
+#[case::no_scope("fix: typo", ["0.1.1", "-"])]
+fn test_repo_workspace_package_scopes(#[case] message: &str, #[case] expected: [&str; 2]) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    fs::write(
+        temp_dir.join("nextsv.toml"),
+        "[packages.two]\nscopes = [\"parser\"]\n",
+    )
+    .unwrap();
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        message,
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("--output json workspace", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let reports: serde_json::Value = serde_json::from_str(&test_result).unwrap();
+    for (report, expected) in reports.as_array().unwrap().iter().zip(expected) {
+        let next_version = report["next_version"].as_str().unwrap_or("-");
+        assert_eq!(expected, next_version, "{}", report["package"]);
+    }
+}
+
+#[rstest]
+#[case::dependency_released("feat: new api", "", "0.1.1 (inherited from one)")]
+#[case::breaking_not_re_exported("feat!: new api", "", "0.1.1 (inherited from one)")]
+#[case::breaking_re_exported("feat!: new api", "--re-export two=one", "0.2.0 (inherited from one)")]
+#[case::level_set(
+    "fix: typo",
+    "--dependency-level feature",
+    "0.1.1 (inherited from one)"
+)]
+#[case::propagation_off("feat: new api", "--dependency-level none", "-")]
+fn test_repo_workspace_dependency_propagation(
+    #[case] message: &str,
+    #[case] options: &str,
+    #[case] expected_two: &str,
+) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(
+        &repo,
+        &temp_dir,
+        "\n[dependencies]\none = { path = \"../one\" }\n",
+    );
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        message,
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(&format!("workspace {options}"), &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    let two = test_result.lines().nth(2).unwrap_or_default();
+    assert!(two.starts_with("two "), "unexpected row: {two}");
+    assert!(two.ends_with(expected_two), "unexpected row: {two}");
+}
+
+#[rstest]
+#[case::package("calculate")]
+#[case::workspace("workspace --dependency-level fix")]
+fn test_repo_major_dependency_bump_warned_once(#[case] arguments: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(
+        &repo,
+        &temp_dir,
+        "\n[dependencies]\none = { path = \"../one\" }\n",
+    );
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "fix(deps): update serde to v2.0.0",
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let output = Command::new(snapbox::cmd::cargo_bin!("nextsv"))
+        .args(arguments.split_ascii_whitespace())
+        .current_dir(&temp_dir)
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    println!("stderr: {stderr}");
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        1,
+        stderr
+            .matches("WARNING: major dependency bump detected: update serde to v2.0.0")
+            .count()
+    );
+}
+
+#[rstest]
+#[case::all_packages(
+    "workspace --lockstep",
+    "package  current  bump   next\none      1.1.0    minor  1.2.0\ntwo      1.1.0    minor  1.2.0\n"
+)]
+#[case::changed_packages(
+    "workspace --lockstep --changed",
+    "package  current  bump   next\ntwo      1.1.0    minor  1.2.0\n"
+)]
+fn test_repo_workspace_lockstep(#[case] arguments: &str, #[case] expected: &str) {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v0.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    let commit = git_utils::find_last_commit(&repo).unwrap();
+    repo.tag_lightweight("v1.1.0", commit.as_object(), false)
+        .unwrap();
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "feat: new api",
+        Some("crates/two/api.rs"),
+    );
+    println!("commit result: {result:?}");
+    let result =
+        git_utils::create_file_and_commit(&repo, temp_dir.clone(), "fix: update readme", None);
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test(arguments, &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(expected, test_result);
+}
+
+#[test]
+fn test_repo_workspace_lockstep_first_version() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("initial");
+    add_test_workspace(&repo, &temp_dir, "");
+
+    let test_result = execute_test("--initial-version 0.2.0 workspace --lockstep", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "package  current  bump   next\none      -        0.2.0  0.2.0\ntwo      -        0.2.0  0.2.0\n",
+        test_result
+    );
+}
+
+#[test]
+fn test_repo_workspace_glob_members_and_inherited_version() {
+    let (temp_dir, repo) = git_utils::create_test_git_directory("v1.1.0");
+    add_test_workspace(&repo, &temp_dir, "");
+    fs::write(
+        temp_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/two\"]\n\n[workspace.package]\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.join("crates/one/Cargo.toml"),
+        "[package]\nname = \"one\"\nversion.workspace = true\n",
+    )
+    .unwrap();
+
+    let result = git_utils::create_file_and_commit(
+        &repo,
+        temp_dir.clone(),
+        "feat: new api",
+        Some("crates/one/api.rs"),
+    );
+    println!("commit result: {result:?}");
+
+    let test_result = execute_test("workspace", &temp_dir);
+
+    let result = fs::remove_dir_all(&temp_dir);
+    println!("remove_dir_all result: {result:?}");
+
+    assert_eq!(
+        "package  current  bump   next\none      0.1.0    patch  0.1.1\n",
+        test_result
+    );
+}